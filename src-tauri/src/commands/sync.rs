@@ -1,6 +1,9 @@
 use crate::error::{ArchivistError, Result};
+use crate::services::backup::BackupNotifyResult;
+use crate::services::backup_audit::{AuditOptions, AuditReport};
 use crate::services::backup_daemon::DaemonState;
 use crate::services::manifest_server::ManifestInfo;
+use crate::services::replication::ReplicationReport;
 use crate::services::sync::{SyncState, WatchedFolder};
 use crate::state::AppState;
 use chrono::Utc;
@@ -85,7 +88,10 @@ pub async fn generate_folder_manifest(
 }
 
 #[tauri::command]
-pub async fn notify_backup_peer(state: State<'_, AppState>, folder_id: String) -> Result<()> {
+pub async fn notify_backup_peer(
+    state: State<'_, AppState>,
+    folder_id: String,
+) -> Result<Vec<BackupNotifyResult>> {
     // Get manifest CID for folder
     let sync = state.sync.read().await;
     let folder = sync
@@ -135,29 +141,64 @@ pub async fn notify_backup_peer(state: State<'_, AppState>, folder_id: String) -
         manifest_cid
     );
 
-    // 2. Get backup peer address and trigger port from config
+    // 2. Get backup peers from config
     let config = state.config.read().await;
-    let app_config = config.get();
-    let backup_addr = app_config
-        .sync
-        .backup_peer_address
-        .clone()
-        .ok_or_else(|| ArchivistError::ConfigError("No backup peer configured".into()))?;
-    let trigger_port = app_config.sync.backup_trigger_port;
+    let backup_peers = config.get().sync.enabled_backup_peers();
     drop(config);
 
-    // 3. Notify backup peer via HTTP trigger
+    if backup_peers.is_empty() {
+        return Err(ArchivistError::ConfigError(
+            "No backup peer configured".into(),
+        ));
+    }
+
+    // 3. Notify each backup peer via HTTP trigger
     let backup = state.backup.read().await;
-    backup
-        .notify_backup_peer(&manifest_cid, &backup_addr, trigger_port)
-        .await?;
+    let results = backup
+        .notify_backup_peers(&folder_id, &manifest_cid, &backup_peers)
+        .await;
+
+    let notified = results.iter().filter(|r| r.success).count();
+    if notified == 0 {
+        let errors: Vec<String> = results
+            .iter()
+            .map(|r| {
+                format!(
+                    "{}: {}",
+                    r.nickname,
+                    r.error.as_deref().unwrap_or("unknown")
+                )
+            })
+            .collect();
+        return Err(ArchivistError::SyncError(format!(
+            "Failed to notify any backup peer ({})",
+            errors.join("; ")
+        )));
+    }
 
     log::info!(
-        "Successfully notified backup peer to poll for manifest: {}",
+        "Notified {}/{} backup peers to poll for manifest: {}",
+        notified,
+        results.len(),
         manifest_cid
     );
 
-    Ok(())
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn get_replication_status(state: State<'_, AppState>) -> Result<ReplicationReport> {
+    let config = state.config.read().await;
+    let sync_settings = config.get().sync;
+    drop(config);
+
+    let manifests = state.manifest_registry.read().await.get_all_manifests();
+    let replication = state.replication.read().await;
+    Ok(replication.report(
+        &manifests,
+        sync_settings.desired_backup_replicas,
+        &sync_settings.enabled_backup_peers(),
+    ))
 }
 
 #[tauri::command]
@@ -284,3 +325,38 @@ Happy archiving!
 
     Ok(quickstart_path.to_string_lossy().to_string())
 }
+
+#[tauri::command]
+pub async fn get_backup_audit_reports(state: State<'_, AppState>) -> Result<Vec<AuditReport>> {
+    let audit = state.backup_audit.read().await;
+    Ok(audit.get_reports())
+}
+
+#[tauri::command]
+pub async fn run_backup_audit(
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+    full: bool,
+) -> Result<AuditReport> {
+    let config = state.config.read().await;
+    let settings = config.get().backup_server;
+    drop(config);
+
+    let options = AuditOptions {
+        sample_size: if full || settings.audit_sample_size == 0 {
+            None
+        } else {
+            Some(settings.audit_sample_size as usize)
+        },
+        verify_content: settings.audit_verify_content,
+    };
+
+    let processed = state.backup_daemon.get_state().await.processed_manifests;
+    crate::services::backup_audit::run_and_record(
+        &state.backup_audit,
+        &processed,
+        options,
+        Some(&app_handle),
+    )
+    .await
+}
@@ -77,6 +77,9 @@ pub enum ArchivistError {
 
     #[error("IRC error: {0}")]
     IrcError(String),
+
+    #[error("Storage error: {0}")]
+    StorageError(String),
 }
 
 impl ArchivistError {
@@ -108,6 +111,7 @@ impl ArchivistError {
             ArchivistError::MarketplaceError(_) => "MARKETPLACE_ERROR",
             ArchivistError::TorrentError(_) => "TORRENT_ERROR",
             ArchivistError::IrcError(_) => "IRC_ERROR",
+            ArchivistError::StorageError(_) => "STORAGE_ERROR",
         }
     }
 }
@@ -6,6 +6,7 @@ pub mod node_api;
 pub mod path_utils;
 pub mod services;
 mod state;
+pub mod storage;
 
 use services::node::NodeManager;
 use services::sync::SyncManager;
@@ -36,6 +37,7 @@ pub fn run() {
     let node_service = app_state.node.clone();
     let sync_service = app_state.sync.clone();
     let backup_daemon = app_state.backup_daemon.clone();
+    let backup_audit = app_state.backup_audit.clone();
     let config_service = app_state.config.clone();
     let manifest_registry = app_state.manifest_registry.clone();
     let manifest_server = app_state.manifest_server.clone();
@@ -117,6 +119,7 @@ pub fn run() {
             commands::pause_sync,
             commands::generate_folder_manifest,
             commands::notify_backup_peer,
+            commands::get_replication_status,
             commands::test_backup_peer_connection,
             commands::create_quickstart_folder,
             // Backup daemon commands
@@ -126,6 +129,8 @@ pub fn run() {
             commands::pause_backup_daemon,
             commands::resume_backup_daemon,
             commands::retry_failed_manifest,
+            commands::get_backup_audit_reports,
+            commands::run_backup_audit,
             // Peer commands
             commands::get_peers,
             commands::connect_peer,
@@ -361,6 +366,54 @@ pub fn run() {
                 trigger_port
             );
 
+            // Schedule periodic backup content audits
+            let config_for_audit = config_service.clone();
+            let backup_daemon_for_audit = backup_daemon_for_server.clone();
+            let app_handle_audit = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    let config = config_for_audit.read().await;
+                    let settings = config.get().backup_server;
+                    drop(config);
+
+                    if settings.audit_interval_hours == 0 {
+                        log::info!("Scheduled backup audits are disabled");
+                        break;
+                    }
+
+                    tokio::time::sleep(tokio::time::Duration::from_secs(
+                        settings.audit_interval_hours * 3600,
+                    ))
+                    .await;
+
+                    if !backup_daemon_for_audit.is_enabled() {
+                        continue;
+                    }
+
+                    let processed = backup_daemon_for_audit
+                        .get_state()
+                        .await
+                        .processed_manifests;
+                    let options = crate::services::backup_audit::AuditOptions {
+                        sample_size: match settings.audit_sample_size {
+                            0 => None,
+                            n => Some(n as usize),
+                        },
+                        verify_content: settings.audit_verify_content,
+                    };
+                    if let Err(e) = crate::services::backup_audit::run_and_record(
+                        &backup_audit,
+                        &processed,
+                        options,
+                        Some(&app_handle_audit),
+                    )
+                    .await
+                    {
+                        log::error!("Scheduled backup audit failed: {}", e);
+                    }
+                }
+            });
+
             // Start media download queue processor
             let media_service_clone = media_service.clone();
             let app_handle_media = app.handle().clone();
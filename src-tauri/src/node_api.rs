@@ -4,14 +4,15 @@
 //! a typed interface to the node's REST API.
 
 use crate::error::{ArchivistError, Result};
-use futures::StreamExt;
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
 use std::error::Error as StdError;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncRead, AsyncWriteExt};
 use tokio_util::io::ReaderStream;
 
 /// Build a detailed error description from a reqwest error by walking the source chain.
@@ -54,6 +55,89 @@ fn describe_reqwest_error(e: &reqwest::Error) -> String {
     desc
 }
 
+/// State record written next to a partial download (`<dest>.part.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartialDownload {
+    cid: String,
+    total_bytes: Option<u64>,
+}
+
+/// Paths of the partial data file and its state record for a download destination
+fn partial_download_paths(dest: &Path) -> (PathBuf, PathBuf) {
+    let mut part = dest.as_os_str().to_owned();
+    part.push(".part");
+    let mut state = part.clone();
+    state.push(".json");
+    (part.into(), state.into())
+}
+
+async fn read_partial_state(path: &Path) -> Option<PartialDownload> {
+    let data = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+async fn write_partial_state(path: &Path, state: &PartialDownload) -> Result<()> {
+    let json = serde_json::to_vec(state)?;
+    tokio::fs::write(path, json).await.map_err(|e| {
+        ArchivistError::FileOperationFailed(format!("Failed to write download state: {}", e))
+    })
+}
+
+async fn discard_partial_download(part_path: &Path, state_path: &Path) {
+    let _ = tokio::fs::remove_file(part_path).await;
+    let _ = tokio::fs::remove_file(state_path).await;
+}
+
+/// Check the partial file's size and move it into place
+///
+/// A short file is kept so the next attempt can resume; an oversized one is
+/// discarded since it can't be trusted.
+async fn finish_partial_download(
+    part_path: &Path,
+    state_path: &Path,
+    dest: &Path,
+    expected_total: Option<u64>,
+) -> Result<()> {
+    let written = tokio::fs::metadata(part_path)
+        .await
+        .map_err(|e| {
+            ArchivistError::FileOperationFailed(format!("Failed to read partial file: {}", e))
+        })?
+        .len();
+
+    if let Some(total) = expected_total {
+        if written < total {
+            return Err(ArchivistError::ApiError(format!(
+                "Download incomplete: {} of {} bytes received, will resume on retry",
+                written, total
+            )));
+        }
+        if written > total {
+            discard_partial_download(part_path, state_path).await;
+            return Err(ArchivistError::FileOperationFailed(format!(
+                "Downloaded file is {} bytes, expected {}",
+                written, total
+            )));
+        }
+    }
+
+    tokio::fs::rename(part_path, dest).await.map_err(|e| {
+        ArchivistError::FileOperationFailed(format!("Failed to move download into place: {}", e))
+    })?;
+    let _ = tokio::fs::remove_file(state_path).await;
+    Ok(())
+}
+
+/// Parse a `Content-Range: bytes <start>-<end>/<total>` header into `(start, total)`
+fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let range = value.strip_prefix("bytes ")?;
+    let (span, total) = range.split_once('/')?;
+    let start = span.split_once('-')?.0.trim().parse().ok()?;
+    let total = total.trim().parse().ok();
+    Some((start, total))
+}
+
 /// Response from /api/archivist/v1/debug/info
 /// Matches archivist-node v0.2.0 API format
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -435,8 +519,6 @@ impl NodeApiClient {
         file_path: &Path,
         app_handle: Option<&tauri::AppHandle>,
     ) -> Result<UploadResponse> {
-        let url = format!("{}/api/archivist/v1/data", self.base_url);
-
         let file = File::open(file_path).await.map_err(|e| {
             ArchivistError::FileOperationFailed(format!("Failed to open file: {}", e))
         })?;
@@ -444,56 +526,116 @@ impl NodeApiClient {
         let file_meta = file.metadata().await.map_err(|e| {
             ArchivistError::FileOperationFailed(format!("Failed to read file metadata: {}", e))
         })?;
-        let file_size = file_meta.len();
 
         let filename = file_path
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
             .unwrap_or_else(|| "file".to_string());
 
+        self.upload_reader(file, &filename, None, Some(file_meta.len()), app_handle)
+            .await
+    }
+
+    /// Upload everything read from `reader` as a single file.
+    ///
+    /// See `upload_stream` for how `mime_type` and `length` are used.
+    pub async fn upload_reader<R>(
+        &self,
+        reader: R,
+        filename: &str,
+        mime_type: Option<&str>,
+        length: Option<u64>,
+        app_handle: Option<&tauri::AppHandle>,
+    ) -> Result<UploadResponse>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        self.upload_stream(
+            ReaderStream::new(reader),
+            filename,
+            mime_type,
+            length,
+            app_handle,
+        )
+        .await
+    }
+
+    /// Upload an in-memory buffer as a single file.
+    pub async fn upload_bytes(
+        &self,
+        data: impl Into<Bytes>,
+        filename: &str,
+        mime_type: Option<&str>,
+    ) -> Result<UploadResponse> {
+        let data = data.into();
+        let length = data.len() as u64;
+        let stream = futures::stream::once(async move { Ok::<_, std::io::Error>(data) });
+        self.upload_stream(stream, filename, mime_type, Some(length), None)
+            .await
+    }
+
+    /// Upload content from any byte stream, so generated data doesn't need a temp file.
+    ///
+    /// `mime_type` defaults to a guess from `filename`. When `length` is known it is
+    /// sent as `Content-Length` and used for progress percentages and the request
+    /// timeout; otherwise the body is sent chunked and progress is reported every 1MB.
+    /// If `app_handle` is provided, emits `upload-progress` events.
+    pub async fn upload_stream<S>(
+        &self,
+        stream: S,
+        filename: &str,
+        mime_type: Option<&str>,
+        length: Option<u64>,
+        app_handle: Option<&tauri::AppHandle>,
+    ) -> Result<UploadResponse>
+    where
+        S: Stream<Item = std::io::Result<Bytes>> + Send + 'static,
+    {
+        let url = format!("{}/api/archivist/v1/data", self.base_url);
+
         // Determine MIME type
-        let mime_type = mime_guess::from_path(file_path)
-            .first()
-            .map(|m| m.to_string())
-            .unwrap_or_else(|| "application/octet-stream".to_string());
+        let mime_type = mime_type.map(str::to_string).unwrap_or_else(|| {
+            mime_guess::from_path(filename)
+                .first()
+                .map(|m| m.to_string())
+                .unwrap_or_else(|| "application/octet-stream".to_string())
+        });
 
         // Build Content-Disposition header for filename
         let content_disposition = format!("attachment; filename=\"{}\"", filename);
 
-        // Stream the file instead of reading it all into memory
-        let reader_stream = ReaderStream::new(file);
-
         // Wrap with progress tracking if app_handle is provided
         let body = if let Some(handle) = app_handle {
             use tauri::Emitter;
             let handle = handle.clone();
-            let fname = filename.clone();
-            let total = file_size;
-            let bytes_sent = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
-            let last_reported = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+            let fname = filename.to_string();
+            let mut bytes_sent: u64 = 0;
+            let mut last_reported: u64 = 0;
 
-            let progress_stream = reader_stream.map(move |chunk| {
+            let progress_stream = stream.map(move |chunk| {
                 if let Ok(ref data) = chunk {
-                    let sent = bytes_sent
-                        .fetch_add(data.len() as u64, std::sync::atomic::Ordering::Relaxed)
-                        + data.len() as u64;
-                    let percent = if total > 0 {
-                        (sent as f64 / total as f64 * 100.0) as u64
-                    } else {
-                        0
-                    };
+                    bytes_sent += data.len() as u64;
+                    let percent = length.map(|total| {
+                        if total > 0 {
+                            (bytes_sent as f64 / total as f64 * 100.0) as u64
+                        } else {
+                            0
+                        }
+                    });
 
-                    // Report every 1% or every 1MB, whichever is less frequent
-                    let last = last_reported.load(std::sync::atomic::Ordering::Relaxed);
-                    let mb_threshold = 1_048_576u64; // 1MB
-                    if percent > last || sent.saturating_sub(last * total / 100) > mb_threshold {
-                        last_reported.store(percent, std::sync::atomic::Ordering::Relaxed);
+                    // Report every 1% when the size is known, otherwise every 1MB
+                    let should_report = match percent {
+                        Some(p) => p > last_reported,
+                        None => bytes_sent.saturating_sub(last_reported * 1_048_576) >= 1_048_576,
+                    };
+                    if should_report {
+                        last_reported = percent.unwrap_or(bytes_sent / 1_048_576);
                         let _ = handle.emit(
                             "upload-progress",
                             serde_json::json!({
                                 "filename": fname,
-                                "bytesSent": sent,
-                                "totalBytes": total,
+                                "bytesSent": bytes_sent,
+                                "totalBytes": length,
                                 "percent": percent
                             }),
                         );
@@ -504,18 +646,22 @@ impl NodeApiClient {
 
             reqwest::Body::wrap_stream(progress_stream)
         } else {
-            reqwest::Body::wrap_stream(reader_stream)
+            reqwest::Body::wrap_stream(stream)
         };
 
-        // Dynamic timeout: at least 300s, or file_size / 10MB/s
-        let timeout_secs = std::cmp::max(300, file_size / (10 * 1024 * 1024));
+        // Dynamic timeout: at least 300s, or size / 10MB/s
+        let timeout_secs = std::cmp::max(300, length.unwrap_or(0) / (10 * 1024 * 1024));
 
-        let response = self
+        let mut request = self
             .client
             .post(&url)
             .header(header::CONTENT_TYPE, &mime_type)
-            .header(header::CONTENT_DISPOSITION, &content_disposition)
-            .header(header::CONTENT_LENGTH, file_size)
+            .header(header::CONTENT_DISPOSITION, &content_disposition);
+        if let Some(length) = length {
+            request = request.header(header::CONTENT_LENGTH, length);
+        }
+
+        let response = request
             .body(body)
             .timeout(Duration::from_secs(timeout_secs))
             .send()
@@ -574,49 +720,24 @@ impl NodeApiClient {
     }
 
     /// Download a file by CID directly to a file path using streaming (constant memory).
+    ///
+    /// Interrupted downloads are resumed on the next call; see
+    /// `download_file_to_path_with_progress`.
     pub async fn download_file_to_path(&self, cid: &str, dest: &Path) -> Result<()> {
-        let url = format!("{}/api/archivist/v1/data/{}", self.base_url, cid);
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
+        self.download_file_to_path_with_progress(cid, dest, None)
             .await
-            .map_err(|e| ArchivistError::ApiError(format!("Download failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(ArchivistError::ApiError(format!(
-                "Download failed: HTTP {}",
-                response.status()
-            )));
-        }
-
-        let mut file = File::create(dest).await.map_err(|e| {
-            ArchivistError::FileOperationFailed(format!("Failed to create file: {}", e))
-        })?;
-
-        let mut stream = response.bytes_stream();
-        while let Some(chunk) = stream.next().await {
-            let data = chunk.map_err(|e| {
-                ArchivistError::ApiError(format!("Failed to read download stream: {}", e))
-            })?;
-            file.write_all(&data).await.map_err(|e| {
-                ArchivistError::FileOperationFailed(format!("Failed to write to file: {}", e))
-            })?;
-        }
-
-        file.flush().await.map_err(|e| {
-            ArchivistError::FileOperationFailed(format!("Failed to flush file: {}", e))
-        })?;
-
-        Ok(())
     }
 
     /// Download a file by CID directly to a file path with progress reporting via Tauri events.
     ///
-    /// Reads `content-length` from the response and emits `download-progress` events
-    /// as chunks are written to disk. If `app_handle` is `None`, behaves identically
-    /// to `download_file_to_path`.
+    /// Data is written to `<dest>.part` alongside a `<dest>.part.json` state record.
+    /// If a previous attempt for the same CID left a partial file, the download
+    /// resumes from its length using an HTTP `Range` request; if the node ignores
+    /// the range, the already-written prefix is skipped in the stream instead of
+    /// being rewritten. The partial file is size-checked before being renamed into
+    /// place. Reads `content-length` from the response and emits `download-progress`
+    /// events as chunks are written to disk. If `app_handle` is `None`, no events
+    /// are emitted.
     pub async fn download_file_to_path_with_progress(
         &self,
         cid: &str,
@@ -624,35 +745,137 @@ impl NodeApiClient {
         app_handle: Option<&tauri::AppHandle>,
     ) -> Result<()> {
         let url = format!("{}/api/archivist/v1/data/{}", self.base_url, cid);
+        let (part_path, state_path) = partial_download_paths(dest);
 
-        let response = self
-            .client
-            .get(&url)
+        // Resume only if the partial file belongs to this CID
+        let previous = read_partial_state(&state_path)
+            .await
+            .filter(|state| state.cid == cid);
+        let mut resume_from = match previous {
+            Some(_) => tokio::fs::metadata(&part_path)
+                .await
+                .map(|m| m.len())
+                .unwrap_or(0),
+            None => 0,
+        };
+        let mut expected_total = previous.and_then(|state| state.total_bytes);
+
+        if resume_from > 0 && expected_total == Some(resume_from) {
+            log::info!("Partial download of {} is already complete", cid);
+            return finish_partial_download(&part_path, &state_path, dest, expected_total).await;
+        }
+
+        let mut request = self.client.get(&url);
+        if resume_from > 0 {
+            request = request.header(header::RANGE, format!("bytes={}-", resume_from));
+        }
+
+        let response = request
             .send()
             .await
             .map_err(|e| ArchivistError::ApiError(format!("Download failed: {}", e)))?;
 
-        if !response.status().is_success() {
-            return Err(ArchivistError::ApiError(format!(
-                "Download failed: HTTP {}",
-                response.status()
-            )));
+        // How many leading bytes of the body to drop (node ignored our Range header)
+        let mut skip_bytes: u64 = 0;
+        match response.status() {
+            reqwest::StatusCode::PARTIAL_CONTENT => {
+                let range = response
+                    .headers()
+                    .get(header::CONTENT_RANGE)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(parse_content_range);
+                match range {
+                    Some((start, total)) if start == resume_from => {
+                        expected_total = total.or(expected_total);
+                    }
+                    _ => {
+                        return Err(ArchivistError::ApiError(format!(
+                            "Download failed: unexpected Content-Range for {} (wanted offset {})",
+                            cid, resume_from
+                        )));
+                    }
+                }
+                log::info!("Resuming download of {} from byte {}", cid, resume_from);
+            }
+            status if status.is_success() => {
+                expected_total = response.content_length();
+                if resume_from > 0 {
+                    log::info!(
+                        "Node ignored range request for {}, skipping {} already-downloaded bytes",
+                        cid,
+                        resume_from
+                    );
+                    skip_bytes = resume_from;
+                }
+            }
+            reqwest::StatusCode::RANGE_NOT_SATISFIABLE => {
+                // Partial file is at least as long as the content; restart cleanly
+                discard_partial_download(&part_path, &state_path).await;
+                return Err(ArchivistError::ApiError(format!(
+                    "Download failed: partial data for {} did not match, retry to start over",
+                    cid
+                )));
+            }
+            status => {
+                return Err(ArchivistError::ApiError(format!(
+                    "Download failed: HTTP {}",
+                    status
+                )));
+            }
         }
 
-        let total_bytes = response.content_length();
+        if let Some(total) = expected_total {
+            if resume_from > total {
+                discard_partial_download(&part_path, &state_path).await;
+                resume_from = 0;
+                skip_bytes = 0;
+                if response.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+                    return Err(ArchivistError::ApiError(format!(
+                        "Download failed: partial data for {} is larger than the content",
+                        cid
+                    )));
+                }
+            }
+        }
 
-        let mut file = File::create(dest).await.map_err(|e| {
-            ArchivistError::FileOperationFailed(format!("Failed to create file: {}", e))
-        })?;
+        write_partial_state(
+            &state_path,
+            &PartialDownload {
+                cid: cid.to_string(),
+                total_bytes: expected_total,
+            },
+        )
+        .await?;
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(resume_from > 0)
+            .write(true)
+            .truncate(resume_from == 0)
+            .open(&part_path)
+            .await
+            .map_err(|e| {
+                ArchivistError::FileOperationFailed(format!("Failed to create file: {}", e))
+            })?;
 
         let mut stream = response.bytes_stream();
-        let mut bytes_received: u64 = 0;
+        let mut bytes_received: u64 = resume_from;
         let mut last_reported_percent: u64 = 0;
 
         while let Some(chunk) = stream.next().await {
-            let data = chunk.map_err(|e| {
+            let mut data = chunk.map_err(|e| {
                 ArchivistError::ApiError(format!("Failed to read download stream: {}", e))
             })?;
+
+            if skip_bytes > 0 {
+                let skip = skip_bytes.min(data.len() as u64);
+                skip_bytes -= skip;
+                data = data.slice(skip as usize..);
+                if data.is_empty() {
+                    continue;
+                }
+            }
+
             file.write_all(&data).await.map_err(|e| {
                 ArchivistError::FileOperationFailed(format!("Failed to write to file: {}", e))
             })?;
@@ -661,7 +884,7 @@ impl NodeApiClient {
 
             if let Some(handle) = app_handle {
                 use tauri::Emitter;
-                let percent = total_bytes.map(|total| {
+                let percent = expected_total.map(|total| {
                     if total > 0 {
                         (bytes_received as f64 / total as f64 * 100.0) as u64
                     } else {
@@ -686,8 +909,9 @@ impl NodeApiClient {
                             "cid": cid,
                             "phase": "saving",
                             "bytesReceived": bytes_received,
-                            "totalBytes": total_bytes,
-                            "percent": percent
+                            "totalBytes": expected_total,
+                            "percent": percent,
+                            "resumedFrom": resume_from
                         }),
                     );
                 }
@@ -697,8 +921,22 @@ impl NodeApiClient {
         file.flush().await.map_err(|e| {
             ArchivistError::FileOperationFailed(format!("Failed to flush file: {}", e))
         })?;
+        file.sync_all().await.map_err(|e| {
+            ArchivistError::FileOperationFailed(format!("Failed to sync file: {}", e))
+        })?;
+        drop(file);
+
+        // Without a length from the node's response, fall back to the dataset size it reports
+        if expected_total.is_none() {
+            expected_total = self
+                .get_file_info(cid)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|info| info.dataset_size);
+        }
 
-        Ok(())
+        finish_partial_download(&part_path, &state_path, dest, expected_total).await
     }
 
     /// Trigger the sidecar to fetch a CID from the P2P network.
@@ -1,31 +1,93 @@
 //! Backup peer notification service
 //!
-//! This service handles notifying a designated backup peer about new manifest files.
-//! It uses HTTP triggers to notify the backup server's daemon to poll immediately,
-//! rather than relying on on-chain persistence features.
+//! This service handles notifying the configured backup peers about new manifest files.
+//! It uses HTTP triggers to notify each backup server's daemon to poll immediately,
+//! rather than relying on on-chain persistence features. Notification outcomes are
+//! recorded in the shared `ReplicationTracker` alongside the peers' acknowledgements.
 
 use crate::error::{ArchivistError, Result};
 use crate::node_api::NodeApiClient;
+use crate::services::config::BackupPeerConfig;
 use crate::services::peers::PeerService;
+use crate::services::replication::ReplicationTracker;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Outcome of notifying a single backup peer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupNotifyResult {
+    pub nickname: String,
+    pub address: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
 /// Service for managing backup peer notifications
 pub struct BackupService {
     #[allow(dead_code)]
     api_client: NodeApiClient,
     peer_service: Arc<RwLock<PeerService>>,
+    replication: Arc<RwLock<ReplicationTracker>>,
 }
 
 impl BackupService {
     /// Create a new BackupService
-    pub fn new(api_client: NodeApiClient, peer_service: Arc<RwLock<PeerService>>) -> Self {
+    pub fn new(
+        api_client: NodeApiClient,
+        peer_service: Arc<RwLock<PeerService>>,
+        replication: Arc<RwLock<ReplicationTracker>>,
+    ) -> Self {
         Self {
             api_client,
             peer_service,
+            replication,
         }
     }
 
+    /// Notify every given backup peer about a folder's new manifest
+    ///
+    /// Peers are notified independently, so one unreachable backup server does
+    /// not prevent the others from being triggered. The outcome for each peer is
+    /// recorded in the replication tracker.
+    pub async fn notify_backup_peers(
+        &self,
+        folder_id: &str,
+        manifest_cid: &str,
+        peers: &[BackupPeerConfig],
+    ) -> Vec<BackupNotifyResult> {
+        let mut results = Vec::with_capacity(peers.len());
+
+        for peer in peers {
+            let outcome = self
+                .notify_backup_peer(manifest_cid, &peer.address, peer.trigger_port)
+                .await;
+            let error = outcome.err().map(|e| e.to_string());
+
+            if let Some(ref e) = error {
+                log::warn!("Failed to notify backup peer {}: {}", peer.nickname, e);
+            }
+
+            let peer_id = Self::extract_peer_id_from_multiaddr(&peer.address)
+                .unwrap_or_else(|| peer.address.clone());
+            let mut tracker = self.replication.write().await;
+            if let Err(e) = tracker.record_notification(folder_id, &peer_id, error.clone()) {
+                log::warn!("Failed to record backup notification: {}", e);
+            }
+            drop(tracker);
+
+            results.push(BackupNotifyResult {
+                nickname: peer.nickname.clone(),
+                address: peer.address.clone(),
+                success: error.is_none(),
+                error,
+            });
+        }
+
+        results
+    }
+
     /// Notify backup peer to poll for new manifests via HTTP trigger
     ///
     /// This ensures the backup peer is connected via P2P (for file transfer)
@@ -115,6 +177,17 @@ impl BackupService {
         )))
     }
 
+    /// Extract the peer ID from the `/p2p/<peer-id>` component of a multiaddr
+    pub fn extract_peer_id_from_multiaddr(multiaddr: &str) -> Option<String> {
+        let parts: Vec<&str> = multiaddr.split('/').collect();
+        parts
+            .iter()
+            .position(|part| *part == "p2p" || *part == "ipfs")
+            .and_then(|i| parts.get(i + 1))
+            .filter(|id| !id.is_empty())
+            .map(|id| id.to_string())
+    }
+
     /// Connect to backup peer if not already connected
     async fn ensure_backup_peer_connected(&self, peer_addr: &str) -> Result<()> {
         log::info!("Ensuring backup peer is connected: {}", peer_addr);
@@ -174,4 +247,18 @@ mod tests {
         let result = BackupService::extract_ip_from_multiaddr("invalid-multiaddr");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_extract_peer_id_from_multiaddr() {
+        assert_eq!(
+            BackupService::extract_peer_id_from_multiaddr(
+                "/ip4/192.168.1.100/tcp/8070/p2p/16Uiu2HAmXYZ"
+            ),
+            Some("16Uiu2HAmXYZ".to_string())
+        );
+        assert_eq!(
+            BackupService::extract_peer_id_from_multiaddr("/ip4/192.168.1.100/tcp/8070"),
+            None
+        );
+    }
 }
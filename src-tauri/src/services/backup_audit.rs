@@ -0,0 +1,473 @@
+//! Periodic content verification for backup servers
+//!
+//! The backup daemon records manifests as processed once their files have been
+//! downloaded, but nothing re-checks those files afterwards. The audit walks the
+//! latest processed manifest of every folder (or a random sample of its files),
+//! checks each CID against the local node and tries to re-fetch anything that
+//! has gone missing. Reports are persisted so the UI can show audit history.
+
+use crate::error::{ArchivistError, Result};
+use crate::node_api::NodeApiClient;
+use crate::services::backup_daemon::ProcessedManifest;
+use chrono::{DateTime, Utc};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::RwLock;
+
+/// Number of audit reports kept on disk
+const MAX_AUDIT_REPORTS: usize = 20;
+
+/// Outcome of auditing one file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditFileStatus {
+    /// Present locally and intact
+    Ok,
+    /// Not present locally and could not be re-fetched from the network
+    Missing,
+    /// Present locally but its size or content doesn't match the manifest
+    Corrupt,
+    /// Was missing locally and has been re-fetched from the network
+    Refetched,
+}
+
+/// A file that did not pass the audit cleanly
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFinding {
+    pub cid: String,
+    pub path: String,
+    pub folder_id: String,
+    pub manifest_cid: String,
+    pub source_peer_id: String,
+    pub status: AuditFileStatus,
+    pub detail: Option<String>,
+}
+
+/// Result of one audit run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditReport {
+    pub id: String,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    /// Whether every file was checked (false when only a sample was audited)
+    pub full_walk: bool,
+    /// Whether local files were re-downloaded to verify their content
+    pub content_verified: bool,
+    pub manifests_checked: u32,
+    pub files_checked: u32,
+    pub ok: u32,
+    pub missing: u32,
+    pub corrupt: u32,
+    pub refetched: u32,
+    /// Manifests that could not be read, so their files were not audited
+    pub unreadable_manifests: Vec<String>,
+    pub findings: Vec<AuditFinding>,
+}
+
+impl AuditReport {
+    /// Whether the audit found data that is no longer retrievable or intact
+    pub fn has_failures(&self) -> bool {
+        self.missing > 0 || self.corrupt > 0 || !self.unreadable_manifests.is_empty()
+    }
+}
+
+/// File entry of a backed-up manifest (only the fields the audit needs)
+#[derive(Debug, Clone, Deserialize)]
+struct AuditManifest {
+    files: Vec<AuditManifestEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AuditManifestEntry {
+    path: String,
+    cid: String,
+    size_bytes: u64,
+}
+
+/// One file selected for auditing
+struct AuditTarget<'a> {
+    manifest: &'a ProcessedManifest,
+    entry: AuditManifestEntry,
+}
+
+/// Options for an audit run
+#[derive(Debug, Clone, Copy)]
+pub struct AuditOptions {
+    /// Audit at most this many files, chosen at random (None = full walk)
+    pub sample_size: Option<usize>,
+    /// Re-download local files to verify their content, not just their size
+    pub verify_content: bool,
+}
+
+/// Service that audits backed-up content and keeps the report history
+pub struct BackupAuditService {
+    api_client: NodeApiClient,
+    reports: Vec<AuditReport>,
+    reports_path: PathBuf,
+}
+
+impl BackupAuditService {
+    /// Create the service, loading previous reports from `data_dir`
+    pub fn new(api_client: NodeApiClient, data_dir: &Path) -> Self {
+        let reports_path = data_dir.join("backup-audit-reports.json");
+        let reports = match std::fs::read_to_string(&reports_path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                log::warn!(
+                    "Failed to parse backup audit reports, starting fresh: {}",
+                    e
+                );
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        Self {
+            api_client,
+            reports,
+            reports_path,
+        }
+    }
+
+    /// All stored reports, newest first
+    pub fn get_reports(&self) -> Vec<AuditReport> {
+        self.reports.iter().rev().cloned().collect()
+    }
+
+    /// The most recent report, if an audit has run
+    #[allow(dead_code)]
+    pub fn latest_report(&self) -> Option<&AuditReport> {
+        self.reports.last()
+    }
+
+    /// Store a report and persist the history
+    pub fn record_report(&mut self, report: AuditReport) -> Result<()> {
+        self.reports.push(report);
+        if self.reports.len() > MAX_AUDIT_REPORTS {
+            let excess = self.reports.len() - MAX_AUDIT_REPORTS;
+            self.reports.drain(..excess);
+        }
+
+        if let Some(parent) = self.reports_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.reports)?;
+        std::fs::write(&self.reports_path, json).map_err(|e| {
+            ArchivistError::FileOperationFailed(format!("Failed to save audit reports: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// Audit the files referenced by the given processed manifests
+    pub async fn run_audit(
+        &self,
+        processed: &HashMap<String, ProcessedManifest>,
+        options: AuditOptions,
+    ) -> Result<AuditReport> {
+        let started_at = Utc::now();
+        let manifests = latest_manifests(processed);
+
+        log::info!(
+            "Starting backup audit of {} manifests ({})",
+            manifests.len(),
+            match options.sample_size {
+                Some(n) => format!("sample of {} files", n),
+                None => "full walk".to_string(),
+            }
+        );
+
+        // Collect the files referenced by each manifest
+        let mut unreadable_manifests = Vec::new();
+        let mut targets = Vec::new();
+        for manifest in &manifests {
+            match self.read_manifest(&manifest.manifest_cid).await {
+                Ok(parsed) => {
+                    targets.extend(
+                        parsed
+                            .files
+                            .into_iter()
+                            .map(|entry| AuditTarget { manifest, entry }),
+                    );
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Audit could not read manifest {}: {}",
+                        manifest.manifest_cid,
+                        e
+                    );
+                    unreadable_manifests.push(manifest.manifest_cid.clone());
+                }
+            }
+        }
+
+        let full_walk = match options.sample_size {
+            Some(n) if n < targets.len() => {
+                targets.shuffle(&mut rand::thread_rng());
+                targets.truncate(n);
+                false
+            }
+            _ => true,
+        };
+
+        let local_sizes: HashMap<String, Option<u64>> = self
+            .api_client
+            .list_data()
+            .await?
+            .content
+            .into_iter()
+            .map(|item| (item.cid, item.manifest.and_then(|m| m.dataset_size)))
+            .collect();
+
+        let mut report = AuditReport {
+            id: uuid::Uuid::new_v4().to_string(),
+            started_at,
+            completed_at: started_at,
+            full_walk,
+            content_verified: options.verify_content,
+            manifests_checked: (manifests.len() - unreadable_manifests.len()) as u32,
+            files_checked: 0,
+            ok: 0,
+            missing: 0,
+            corrupt: 0,
+            refetched: 0,
+            unreadable_manifests,
+            findings: Vec::new(),
+        };
+
+        let mut seen = HashSet::new();
+        for target in targets {
+            if !seen.insert(target.entry.cid.clone()) {
+                continue;
+            }
+
+            let (status, detail) = self
+                .check_file(&target.entry, &local_sizes, options.verify_content)
+                .await;
+
+            report.files_checked += 1;
+            match status {
+                AuditFileStatus::Ok => report.ok += 1,
+                AuditFileStatus::Missing => report.missing += 1,
+                AuditFileStatus::Corrupt => report.corrupt += 1,
+                AuditFileStatus::Refetched => report.refetched += 1,
+            }
+
+            if status != AuditFileStatus::Ok {
+                report.findings.push(AuditFinding {
+                    cid: target.entry.cid,
+                    path: target.entry.path,
+                    folder_id: target.manifest.folder_id.clone(),
+                    manifest_cid: target.manifest.manifest_cid.clone(),
+                    source_peer_id: target.manifest.source_peer_id.clone(),
+                    status,
+                    detail,
+                });
+            }
+        }
+
+        report.completed_at = Utc::now();
+
+        log::info!(
+            "Backup audit finished: {} files checked, {} ok, {} missing, {} corrupt, {} re-fetched",
+            report.files_checked,
+            report.ok,
+            report.missing,
+            report.corrupt,
+            report.refetched
+        );
+
+        Ok(report)
+    }
+
+    /// Download and parse a manifest from the local node
+    async fn read_manifest(&self, manifest_cid: &str) -> Result<AuditManifest> {
+        let bytes = self.api_client.download_file(manifest_cid).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Check a single file against the local node, re-fetching it if missing
+    async fn check_file(
+        &self,
+        entry: &AuditManifestEntry,
+        local_sizes: &HashMap<String, Option<u64>>,
+        verify_content: bool,
+    ) -> (AuditFileStatus, Option<String>) {
+        let Some(local_size) = local_sizes.get(&entry.cid) else {
+            return match self.api_client.request_network_download(&entry.cid).await {
+                Ok(_) => (
+                    AuditFileStatus::Refetched,
+                    Some("Missing locally, re-fetched from network".to_string()),
+                ),
+                Err(e) => (
+                    AuditFileStatus::Missing,
+                    Some(format!("Re-fetch failed: {}", e)),
+                ),
+            };
+        };
+
+        if let Some(size) = local_size {
+            if *size != entry.size_bytes {
+                return (
+                    AuditFileStatus::Corrupt,
+                    Some(format!(
+                        "Node reports {} bytes, manifest expects {}",
+                        size, entry.size_bytes
+                    )),
+                );
+            }
+        }
+
+        if verify_content {
+            // The node verifies blocks against the CID as it serves them
+            match self.api_client.download_file(&entry.cid).await {
+                Ok(bytes) if bytes.len() as u64 == entry.size_bytes => {}
+                Ok(bytes) => {
+                    return (
+                        AuditFileStatus::Corrupt,
+                        Some(format!(
+                            "Downloaded {} bytes, manifest expects {}",
+                            bytes.len(),
+                            entry.size_bytes
+                        )),
+                    );
+                }
+                Err(e) => {
+                    return (
+                        AuditFileStatus::Corrupt,
+                        Some(format!("Local read failed: {}", e)),
+                    );
+                }
+            }
+        }
+
+        (AuditFileStatus::Ok, None)
+    }
+}
+
+/// Run an audit, persist its report and emit `backup-audit-failed` if it found problems
+pub async fn run_and_record(
+    audit: &Arc<RwLock<BackupAuditService>>,
+    processed: &HashMap<String, ProcessedManifest>,
+    options: AuditOptions,
+    app_handle: Option<&AppHandle>,
+) -> Result<AuditReport> {
+    let report = audit.read().await.run_audit(processed, options).await?;
+
+    audit.write().await.record_report(report.clone())?;
+
+    if report.has_failures() {
+        log::warn!(
+            "Backup audit {} found {} missing and {} corrupt files",
+            report.id,
+            report.missing,
+            report.corrupt
+        );
+        if let Some(handle) = app_handle {
+            let _ = handle.emit("backup-audit-failed", &report);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Latest processed manifest per source peer and folder
+///
+/// Older manifests for a folder are superseded, and files removed since then
+/// have been deleted on purpose, so only the newest one is audited.
+fn latest_manifests(processed: &HashMap<String, ProcessedManifest>) -> Vec<ProcessedManifest> {
+    let mut latest: HashMap<(&str, &str), &ProcessedManifest> = HashMap::new();
+    for m in processed.values() {
+        let key = (m.source_peer_id.as_str(), m.folder_id.as_str());
+        match latest.get(&key) {
+            Some(existing) if existing.sequence_number >= m.sequence_number => {}
+            _ => {
+                latest.insert(key, m);
+            }
+        }
+    }
+
+    let mut manifests: Vec<ProcessedManifest> = latest.into_values().cloned().collect();
+    manifests.sort_by(|a, b| a.folder_id.cmp(&b.folder_id));
+    manifests
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn processed(cid: &str, peer: &str, folder: &str, seq: u64) -> ProcessedManifest {
+        ProcessedManifest {
+            manifest_cid: cid.to_string(),
+            source_peer_id: peer.to_string(),
+            sequence_number: seq,
+            folder_id: folder.to_string(),
+            processed_at: Utc::now(),
+            file_count: 0,
+            total_size_bytes: 0,
+            deleted_count: 0,
+            acknowledged: true,
+        }
+    }
+
+    fn report(missing: u32) -> AuditReport {
+        AuditReport {
+            id: uuid::Uuid::new_v4().to_string(),
+            started_at: Utc::now(),
+            completed_at: Utc::now(),
+            full_walk: true,
+            content_verified: false,
+            manifests_checked: 1,
+            files_checked: 1,
+            ok: 1 - missing,
+            missing,
+            corrupt: 0,
+            refetched: 0,
+            unreadable_manifests: Vec::new(),
+            findings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_latest_manifests_skips_superseded() {
+        let mut map = HashMap::new();
+        for m in [
+            processed("p1", "peer-a", "photos", 1),
+            processed("p2", "peer-a", "photos", 2),
+            processed("d1", "peer-a", "docs", 4),
+            processed("o1", "peer-b", "photos", 1),
+        ] {
+            map.insert(m.manifest_cid.clone(), m);
+        }
+
+        let mut cids: Vec<String> = latest_manifests(&map)
+            .into_iter()
+            .map(|m| m.manifest_cid)
+            .collect();
+        cids.sort();
+        assert_eq!(cids, vec!["d1", "o1", "p2"]);
+    }
+
+    #[test]
+    fn test_reports_persist_and_are_capped() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let mut audit = BackupAuditService::new(NodeApiClient::new(8080), tmp.path());
+            for _ in 0..MAX_AUDIT_REPORTS {
+                audit.record_report(report(0)).unwrap();
+            }
+            audit.record_report(report(1)).unwrap();
+        }
+
+        let audit = BackupAuditService::new(NodeApiClient::new(8080), tmp.path());
+        let reports = audit.get_reports();
+        assert_eq!(reports.len(), MAX_AUDIT_REPORTS);
+        assert!(reports[0].has_failures());
+        assert!(!reports[1].has_failures());
+    }
+}
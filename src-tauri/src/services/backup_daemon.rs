@@ -6,17 +6,22 @@
 //! - Parses manifests to extract file lists and deletions
 //! - Downloads missing files from the network
 //! - Enforces deletions based on tombstones
-//! - Tracks processing state with sequence numbers
+//! - Tracks processing state with sequence numbers, walking back through
+//!   predecessor links to process any skipped manifests in order
 //! - Accepts trigger notifications from source peers via HTTP
+//! - Acknowledges processed manifests back to the source peer
 
 use crate::error::{ArchivistError, Result};
 use crate::node_api::NodeApiClient;
+use crate::services::backup::BackupService;
 use crate::services::config::SourcePeerConfig;
 use crate::services::manifest_server::ManifestClient;
+use crate::services::replication::ManifestAck;
+use crate::storage::{self, Store};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
@@ -35,6 +40,10 @@ pub struct DaemonState {
     /// Manifests that failed processing (need retry)
     pub failed_manifests: Vec<FailedManifest>,
 
+    /// Manifests rejected because they would exceed their source's quota
+    #[serde(default)]
+    pub quota_exceeded_manifests: Vec<QuotaExceededManifest>,
+
     /// Last time we polled for new manifests
     pub last_poll_time: DateTime<Utc>,
 
@@ -52,6 +61,9 @@ pub struct ProcessedManifest {
     pub file_count: u32,
     pub total_size_bytes: u64,
     pub deleted_count: u32,
+    /// Whether the source peer has accepted our acknowledgement
+    #[serde(default)]
+    pub acknowledged: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,6 +89,31 @@ pub struct FailedManifest {
     pub multiaddr: Option<String>,
 }
 
+/// Manifest not downloaded because it would push its source over quota
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaExceededManifest {
+    pub manifest_cid: String,
+    pub source_peer_id: String,
+    pub folder_id: String,
+    pub sequence_number: u64,
+    pub rejected_at: DateTime<Utc>,
+    /// Bytes the source would use after applying this manifest
+    pub projected_bytes: u64,
+    pub max_bytes: Option<u64>,
+    /// Files the source would have after applying this manifest
+    pub projected_files: u32,
+    pub max_files: Option<u32>,
+    #[serde(default)]
+    pub multiaddr: Option<String>,
+}
+
+/// Storage used on this server by one source peer
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct SourceUsage {
+    pub bytes: u64,
+    pub files: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DaemonStats {
     pub total_manifests_processed: u64,
@@ -84,6 +121,15 @@ pub struct DaemonStats {
     pub total_bytes_downloaded: u64,
     pub total_files_deleted: u64,
     pub last_activity_at: Option<DateTime<Utc>>,
+    /// Skipped manifests recovered by walking back through sequence gaps
+    #[serde(default)]
+    pub gap_manifests_recovered: u64,
+    /// Manifests rejected for exceeding a source quota
+    #[serde(default)]
+    pub quota_rejections: u64,
+    /// Current usage per source peer ID, from the latest manifest of each folder
+    #[serde(default)]
+    pub source_usage: HashMap<String, SourceUsage>,
 }
 
 impl Default for DaemonState {
@@ -92,12 +138,71 @@ impl Default for DaemonState {
             processed_manifests: HashMap::new(),
             in_progress_manifests: HashMap::new(),
             failed_manifests: Vec::new(),
+            quota_exceeded_manifests: Vec::new(),
             last_poll_time: Utc::now(),
             stats: DaemonStats::default(),
         }
     }
 }
 
+impl DaemonState {
+    /// Storage used per source peer, counting only the latest processed manifest of each folder
+    ///
+    /// Manifests describe a folder's full contents, so older manifests for the
+    /// same folder are superseded rather than additive.
+    fn compute_source_usage(&self) -> HashMap<String, SourceUsage> {
+        let mut latest: HashMap<(&str, &str), &ProcessedManifest> = HashMap::new();
+        for m in self.processed_manifests.values() {
+            let key = (m.source_peer_id.as_str(), m.folder_id.as_str());
+            match latest.get(&key) {
+                Some(existing) if existing.sequence_number >= m.sequence_number => {}
+                _ => {
+                    latest.insert(key, m);
+                }
+            }
+        }
+
+        let mut usage: HashMap<String, SourceUsage> = HashMap::new();
+        for ((peer_id, _), m) in latest {
+            let entry = usage.entry(peer_id.to_string()).or_default();
+            entry.bytes += m.total_size_bytes;
+            entry.files += m.file_count;
+        }
+        usage
+    }
+
+    /// Usage a source would have if `folder_id` were replaced by a manifest of the given size
+    fn projected_source_usage(
+        &self,
+        source_peer_id: &str,
+        folder_id: &str,
+        bytes: u64,
+        files: u32,
+    ) -> SourceUsage {
+        let mut latest: HashMap<&str, &ProcessedManifest> = HashMap::new();
+        for m in self
+            .processed_manifests
+            .values()
+            .filter(|m| m.source_peer_id == source_peer_id && m.folder_id != folder_id)
+        {
+            match latest.get(m.folder_id.as_str()) {
+                Some(existing) if existing.sequence_number >= m.sequence_number => {}
+                _ => {
+                    latest.insert(m.folder_id.as_str(), m);
+                }
+            }
+        }
+
+        latest
+            .values()
+            .fold(SourceUsage { bytes, files }, |mut acc, m| {
+                acc.bytes += m.total_size_bytes;
+                acc.files += m.file_count;
+                acc
+            })
+    }
+}
+
 /// Manifest file structure (JSON) - must match primary peer's ManifestFile
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ManifestFile {
@@ -108,6 +213,9 @@ struct ManifestFile {
     pub sequence_number: u64,
     pub last_updated: DateTime<Utc>,
     pub manifest_cid: Option<String>,
+    /// CID of the source's previous manifest (absent in manifests from older versions)
+    #[serde(default)]
+    pub previous_manifest_cid: Option<String>,
     pub files: Vec<ManifestFileEntry>,
     pub deleted_files: Vec<ManifestDeletedEntry>,
     pub stats: ManifestStats,
@@ -144,6 +252,7 @@ struct DiscoveredManifest {
     pub sequence_number: u64,
     pub source_peer_id: String,
     pub source_host: String,
+    pub source_port: u16,
     pub multiaddr: Option<String>,
 }
 
@@ -165,12 +274,57 @@ struct DeletionResult {
     pub not_found: u32,
 }
 
+/// Everything in `DaemonState` except processed manifests, which are stored one row each
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredDaemonState {
+    in_progress_manifests: HashMap<String, InProgressManifest>,
+    failed_manifests: Vec<FailedManifest>,
+    #[serde(default)]
+    quota_exceeded_manifests: Vec<QuotaExceededManifest>,
+    last_poll_time: DateTime<Utc>,
+    stats: DaemonStats,
+}
+
+impl StoredDaemonState {
+    fn from_state(state: &DaemonState) -> Self {
+        Self {
+            in_progress_manifests: state.in_progress_manifests.clone(),
+            failed_manifests: state.failed_manifests.clone(),
+            quota_exceeded_manifests: state.quota_exceeded_manifests.clone(),
+            last_poll_time: state.last_poll_time,
+            stats: state.stats.clone(),
+        }
+    }
+
+    fn into_state(self, processed: Vec<ProcessedManifest>) -> DaemonState {
+        DaemonState {
+            processed_manifests: processed
+                .into_iter()
+                .map(|m| (m.manifest_cid.clone(), m))
+                .collect(),
+            in_progress_manifests: self.in_progress_manifests,
+            failed_manifests: self.failed_manifests,
+            quota_exceeded_manifests: self.quota_exceeded_manifests,
+            last_poll_time: self.last_poll_time,
+            stats: self.stats,
+        }
+    }
+}
+
+/// Database namespaces for daemon state
+const STATE_NAMESPACE: &str = "backup_daemon";
+const STATE_KEY: &str = "state";
+const PROCESSED_NAMESPACE: &str = "backup_daemon.processed";
+
+/// Maximum number of skipped manifests fetched when recovering a sequence gap
+const MAX_GAP_RECOVERY_DEPTH: usize = 100;
+
 /// Backup daemon for automatic manifest processing
 pub struct BackupDaemon {
     api_client: NodeApiClient,
     manifest_client: ManifestClient,
     state: Arc<RwLock<DaemonState>>,
-    state_file_path: PathBuf,
+    store: Store,
     enabled: Arc<AtomicBool>,
     poll_interval_secs: u64,
     max_concurrent_downloads: u32,
@@ -178,6 +332,8 @@ pub struct BackupDaemon {
     auto_delete_tombstones: bool,
     /// Source peers to poll for manifests
     source_peers: Arc<RwLock<Vec<SourcePeerConfig>>>,
+    /// Source peer configs keyed by the peer ID their manifest server reported
+    discovered_sources: Arc<RwLock<HashMap<String, SourcePeerConfig>>>,
     /// Port for HTTP trigger server
     trigger_port: u16,
     /// Channel to send trigger signals to the main loop
@@ -188,8 +344,10 @@ pub struct BackupDaemon {
 
 impl BackupDaemon {
     /// Create a new backup daemon
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         api_client: NodeApiClient,
+        store: Store,
         enabled: bool,
         poll_interval_secs: u64,
         max_concurrent_downloads: u32,
@@ -197,11 +355,10 @@ impl BackupDaemon {
         auto_delete_tombstones: bool,
         trigger_port: u16,
     ) -> Self {
-        let state_path = dirs::data_dir()
-            .map(|p| p.join("archivist").join("backup-daemon-state.json"))
-            .unwrap_or_else(|| PathBuf::from("backup-daemon-state.json"));
-
-        let state = Self::load_state(&state_path).unwrap_or_default();
+        let state = Self::load_state(&store).unwrap_or_else(|e| {
+            log::error!("Failed to load backup daemon state, starting fresh: {}", e);
+            DaemonState::default()
+        });
 
         // Create trigger channel (buffer of 10 to avoid blocking)
         let (trigger_tx, trigger_rx) = mpsc::channel(10);
@@ -210,13 +367,14 @@ impl BackupDaemon {
             api_client,
             manifest_client: ManifestClient::new(),
             state: Arc::new(RwLock::new(state)),
-            state_file_path: state_path,
+            store,
             enabled: Arc::new(AtomicBool::new(enabled)),
             poll_interval_secs,
             max_concurrent_downloads,
             max_retries,
             auto_delete_tombstones,
             source_peers: Arc::new(RwLock::new(Vec::new())),
+            discovered_sources: Arc::new(RwLock::new(HashMap::new())),
             trigger_port,
             trigger_tx,
             trigger_rx: Arc::new(RwLock::new(trigger_rx)),
@@ -228,6 +386,17 @@ impl BackupDaemon {
         let mut source_peers = self.source_peers.write().await;
         *source_peers = peers;
         log::info!("Updated source peers: {} configured", source_peers.len());
+        drop(source_peers);
+
+        // Quotas may have changed, so give rejected manifests another chance
+        let mut state = self.state.write().await;
+        if !state.quota_exceeded_manifests.is_empty() {
+            log::info!(
+                "Re-evaluating {} manifests previously rejected for quota",
+                state.quota_exceeded_manifests.len()
+            );
+            state.quota_exceeded_manifests.clear();
+        }
     }
 
     /// Add a source peer
@@ -238,19 +407,33 @@ impl BackupDaemon {
         log::info!("Added source peer, now {} configured", source_peers.len());
     }
 
-    /// Load state from disk
-    fn load_state(path: &Path) -> Result<DaemonState> {
-        if !path.exists() {
-            log::info!("No existing daemon state found, starting fresh");
-            return Ok(DaemonState::default());
-        }
-
-        let contents = std::fs::read_to_string(path).map_err(|e| {
-            ArchivistError::FileOperationFailed(format!("Failed to read daemon state: {}", e))
-        })?;
+    /// Load state from the database, importing the legacy JSON state file on first run
+    fn load_state(store: &Store) -> Result<DaemonState> {
+        let stored: Option<StoredDaemonState> = store.get_document(STATE_NAMESPACE, STATE_KEY)?;
 
-        let state: DaemonState =
-            serde_json::from_str(&contents).map_err(ArchivistError::SerializationError)?;
+        let state = match stored {
+            Some(stored) => {
+                let processed: Vec<ProcessedManifest> =
+                    store.list_documents(PROCESSED_NAMESPACE)?;
+                stored.into_state(processed)
+            }
+            None => {
+                let legacy_path = dirs::data_dir()
+                    .map(|p| p.join("archivist").join("backup-daemon-state.json"))
+                    .unwrap_or_else(|| PathBuf::from("backup-daemon-state.json"));
+
+                match storage::take_legacy_json::<DaemonState>(&legacy_path) {
+                    Some(state) => {
+                        Self::write_state(store, &state, state.processed_manifests.keys())?;
+                        state
+                    }
+                    None => {
+                        log::info!("No existing daemon state found, starting fresh");
+                        return Ok(DaemonState::default());
+                    }
+                }
+            }
+        };
 
         log::info!(
             "Loaded daemon state: {} processed, {} in-progress, {} failed",
@@ -262,33 +445,48 @@ impl BackupDaemon {
         Ok(state)
     }
 
-    /// Save state to disk
+    /// Write the daemon state plus the given processed manifests in one transaction
+    fn write_state<'a>(
+        store: &Store,
+        state: &DaemonState,
+        processed_cids: impl IntoIterator<Item = &'a String>,
+    ) -> Result<()> {
+        store.transaction(|tx| {
+            storage::put_document(
+                tx,
+                STATE_NAMESPACE,
+                STATE_KEY,
+                &StoredDaemonState::from_state(state),
+            )?;
+            for cid in processed_cids {
+                if let Some(processed) = state.processed_manifests.get(cid) {
+                    storage::put_document(tx, PROCESSED_NAMESPACE, cid, processed)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Save state to the database
+    ///
+    /// Processed manifests are stored one row each and are only written when
+    /// they change (see `save_processed`), so this doesn't grow with history.
     async fn save_state(&self) -> Result<()> {
         let state = self.state.read().await;
+        Self::write_state(&self.store, &state, std::iter::empty())
+    }
 
-        // Ensure parent directory exists
-        if let Some(parent) = self.state_file_path.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| {
-                ArchivistError::FileOperationFailed(format!(
-                    "Failed to create state directory: {}",
-                    e
-                ))
-            })?;
-        }
-
-        let json =
-            serde_json::to_string_pretty(&*state).map_err(ArchivistError::SerializationError)?;
-
-        std::fs::write(&self.state_file_path, json).map_err(|e| {
-            ArchivistError::FileOperationFailed(format!("Failed to write daemon state: {}", e))
-        })?;
-
-        Ok(())
+    /// Save state together with newly added or updated processed manifests
+    async fn save_processed(&self, cids: &[String]) -> Result<()> {
+        let state = self.state.read().await;
+        Self::write_state(&self.store, &state, cids)
     }
 
     /// Get current daemon state (for UI)
     pub async fn get_state(&self) -> DaemonState {
-        self.state.read().await.clone()
+        let mut state = self.state.read().await.clone();
+        state.stats.source_usage = state.compute_source_usage();
+        state
     }
 
     /// Enable the daemon
@@ -396,6 +594,11 @@ impl BackupDaemon {
                         response.peer_id
                     );
 
+                    self.discovered_sources
+                        .write()
+                        .await
+                        .insert(response.peer_id.clone(), peer.clone());
+
                     for manifest in response.manifests {
                         discovered.push(DiscoveredManifest {
                             cid: manifest.manifest_cid,
@@ -403,6 +606,7 @@ impl BackupDaemon {
                             sequence_number: manifest.sequence_number,
                             source_peer_id: response.peer_id.clone(),
                             source_host: peer.host.clone(),
+                            source_port: peer.manifest_port,
                             multiaddr: peer.multiaddr.clone(),
                         });
                     }
@@ -436,6 +640,12 @@ impl BackupDaemon {
             .into_iter()
             .filter(|m| !state.processed_manifests.contains_key(&m.cid))
             .filter(|m| !state.in_progress_manifests.contains_key(&m.cid))
+            .filter(|m| {
+                !state
+                    .quota_exceeded_manifests
+                    .iter()
+                    .any(|q| q.manifest_cid == m.cid)
+            })
             .collect()
     }
 
@@ -466,7 +676,27 @@ impl BackupDaemon {
             }
         }
 
-        // 1. Try to download manifest from local storage first, then from network
+        // 1. Fetch and parse the manifest
+        let manifest = self.fetch_manifest(manifest_cid).await?;
+
+        log::info!(
+            "Manifest from peer {} folder {} sequence {} with {} files",
+            manifest.source_peer_id,
+            manifest.folder_id,
+            manifest.sequence_number,
+            manifest.files.len()
+        );
+
+        // 2. Process any manifests skipped since the last one we applied, oldest first
+        self.recover_sequence_gap(&manifest, multiaddr).await?;
+
+        // 3. Apply this manifest
+        self.apply_manifest(manifest_cid, &manifest, multiaddr)
+            .await
+    }
+
+    /// Download a manifest (local storage first, then network) and parse it
+    async fn fetch_manifest(&self, manifest_cid: &str) -> Result<ManifestFile> {
         let manifest_bytes = match self.api_client.download_file(manifest_cid).await {
             Ok(bytes) => {
                 log::debug!("Manifest {} found in local storage", manifest_cid);
@@ -486,20 +716,25 @@ impl BackupDaemon {
 
         let manifest_json = String::from_utf8(manifest_bytes)
             .map_err(|e| ArchivistError::SyncError(format!("Invalid UTF-8 in manifest: {}", e)))?;
-        let manifest: ManifestFile = serde_json::from_str(&manifest_json)?;
-
-        log::info!(
-            "Manifest from peer {} folder {} sequence {} with {} files",
-            manifest.source_peer_id,
-            manifest.folder_id,
-            manifest.sequence_number,
-            manifest.files.len()
-        );
+        Ok(serde_json::from_str(&manifest_json)?)
+    }
 
-        // 2. Validate sequence number (check for gaps)
-        self.validate_sequence_number(&manifest).await?;
+    /// Download files, enforce deletions and record the outcome for one manifest
+    async fn apply_manifest(
+        &self,
+        manifest_cid: &str,
+        manifest: &ManifestFile,
+        multiaddr: Option<&str>,
+    ) -> Result<()> {
+        // 0. Refuse manifests that would push the source over its quota
+        if self
+            .check_source_quota(manifest_cid, manifest, multiaddr)
+            .await?
+        {
+            return Ok(());
+        }
 
-        // 3. Mark as in-progress
+        // 1. Mark as in-progress
         {
             let mut state = self.state.write().await;
             state.in_progress_manifests.insert(
@@ -518,12 +753,12 @@ impl BackupDaemon {
         }
         self.save_state().await?;
 
-        // 4. Download all files
-        let download_result = self.download_manifest_files(&manifest).await;
+        // 2. Download all files
+        let download_result = self.download_manifest_files(manifest).await;
 
-        // 5. Enforce deletions (if enabled)
+        // 3. Enforce deletions (if enabled)
         let deletion_result = if self.auto_delete_tombstones {
-            self.enforce_deletions(&manifest).await
+            self.enforce_deletions(manifest).await
         } else {
             Ok(DeletionResult {
                 deleted: 0,
@@ -532,10 +767,10 @@ impl BackupDaemon {
             })
         };
 
-        // 6. Mark as processed (or failed)
+        // 4. Mark as processed (or failed)
         self.finalize_manifest_processing(
             manifest_cid,
-            &manifest,
+            manifest,
             download_result,
             deletion_result,
             multiaddr,
@@ -545,34 +780,208 @@ impl BackupDaemon {
         Ok(())
     }
 
-    /// Validate sequence number to detect gaps
-    async fn validate_sequence_number(&self, manifest: &ManifestFile) -> Result<()> {
-        let state = self.state.read().await;
+    /// Find the source peer config (and its quota) for a peer ID
+    async fn source_config_for(&self, source_peer_id: &str) -> Option<SourcePeerConfig> {
+        if let Some(config) = self.discovered_sources.read().await.get(source_peer_id) {
+            return Some(config.clone());
+        }
+
+        let source_peers = self.source_peers.read().await;
+        source_peers
+            .iter()
+            .find(|p| {
+                p.peer_id.as_deref() == Some(source_peer_id)
+                    || p.multiaddr.as_deref().is_some_and(|addr| {
+                        BackupService::extract_peer_id_from_multiaddr(addr).as_deref()
+                            == Some(source_peer_id)
+                    })
+            })
+            .cloned()
+    }
+
+    /// Check a manifest against its source's quota before downloading anything
+    ///
+    /// Returns `true` (and records the rejection) if the manifest would exceed
+    /// the quota.
+    async fn check_source_quota(
+        &self,
+        manifest_cid: &str,
+        manifest: &ManifestFile,
+        multiaddr: Option<&str>,
+    ) -> Result<bool> {
+        let Some(config) = self.source_config_for(&manifest.source_peer_id).await else {
+            return Ok(false);
+        };
+        if config.max_bytes.is_none() && config.max_files.is_none() {
+            return Ok(false);
+        }
+
+        let mut state = self.state.write().await;
+        let projected = state.projected_source_usage(
+            &manifest.source_peer_id,
+            &manifest.folder_id,
+            manifest.stats.total_size_bytes,
+            manifest.files.len() as u32,
+        );
+
+        if !quota_exceeded(&projected, config.max_bytes, config.max_files) {
+            return Ok(false);
+        }
 
-        // Find last processed manifest from this source peer + folder
-        let last_seq = state
+        log::warn!(
+            "Manifest {} from {} exceeds quota: {} bytes / {} files (limits {:?} bytes / {:?} files)",
+            manifest_cid,
+            config.nickname,
+            projected.bytes,
+            projected.files,
+            config.max_bytes,
+            config.max_files
+        );
+
+        state
+            .quota_exceeded_manifests
+            .retain(|q| q.manifest_cid != manifest_cid);
+        state.quota_exceeded_manifests.push(QuotaExceededManifest {
+            manifest_cid: manifest_cid.to_string(),
+            source_peer_id: manifest.source_peer_id.clone(),
+            folder_id: manifest.folder_id.clone(),
+            sequence_number: manifest.sequence_number,
+            rejected_at: Utc::now(),
+            projected_bytes: projected.bytes,
+            max_bytes: config.max_bytes,
+            projected_files: projected.files,
+            max_files: config.max_files,
+            multiaddr: multiaddr.map(|s| s.to_string()),
+        });
+        state.stats.quota_rejections += 1;
+        drop(state);
+
+        self.save_state().await?;
+        Ok(true)
+    }
+
+    /// Highest sequence number processed for a source peer's folder
+    async fn last_processed_sequence(&self, source_peer_id: &str, folder_id: &str) -> Option<u64> {
+        let state = self.state.read().await;
+        state
             .processed_manifests
             .values()
-            .filter(|m| m.source_peer_id == manifest.source_peer_id)
-            .filter(|m| m.folder_id == manifest.folder_id)
+            .filter(|m| m.source_peer_id == source_peer_id)
+            .filter(|m| m.folder_id == folder_id)
             .map(|m| m.sequence_number)
-            .max();
+            .max()
+    }
 
-        if let Some(last) = last_seq {
-            let expected = last + 1;
-            if manifest.sequence_number > expected {
+    /// Detect a sequence gap and process the skipped manifests in order
+    ///
+    /// Skipped manifests are found by following `previous_manifest_cid` links
+    /// back from the new manifest. Their tombstones would otherwise never be
+    /// applied, since the source only includes deletions in the manifest
+    /// generated right after them. Manifests from older sources carry no link,
+    /// in which case the gap is logged and processing continues.
+    async fn recover_sequence_gap(
+        &self,
+        manifest: &ManifestFile,
+        multiaddr: Option<&str>,
+    ) -> Result<()> {
+        let last_seq = self
+            .last_processed_sequence(&manifest.source_peer_id, &manifest.folder_id)
+            .await;
+
+        let Some((first_missing, last_missing)) = sequence_gap(last_seq, manifest.sequence_number)
+        else {
+            return Ok(());
+        };
+
+        log::warn!(
+            "Sequence gap detected for peer {} folder {}: missing {}..={}, walking back",
+            manifest.source_peer_id,
+            manifest.folder_id,
+            first_missing,
+            last_missing
+        );
+
+        // Walk back until we reach a manifest we've already processed
+        let mut chain: Vec<(String, ManifestFile)> = Vec::new();
+        let mut cursor = manifest.previous_manifest_cid.clone();
+
+        while let Some(cid) = cursor {
+            if chain.len() >= MAX_GAP_RECOVERY_DEPTH {
                 log::warn!(
-                    "Sequence gap detected for peer {} folder {}: expected {}, got {} (gap of {})",
-                    manifest.source_peer_id,
+                    "Gap recovery for folder {} stopped after {} manifests",
                     manifest.folder_id,
-                    expected,
-                    manifest.sequence_number,
-                    manifest.sequence_number - expected
+                    MAX_GAP_RECOVERY_DEPTH
+                );
+                break;
+            }
+
+            if self
+                .state
+                .read()
+                .await
+                .processed_manifests
+                .contains_key(&cid)
+            {
+                break;
+            }
+
+            let previous = self.fetch_manifest(&cid).await.map_err(|e| {
+                ArchivistError::SyncError(format!(
+                    "Failed to fetch skipped manifest {} for folder {}: {}",
+                    cid, manifest.folder_id, e
+                ))
+            })?;
+
+            if previous.folder_id != manifest.folder_id
+                || previous.sequence_number >= manifest.sequence_number
+            {
+                log::warn!(
+                    "Manifest {} is not a predecessor of folder {} seq {}, stopping gap recovery",
+                    cid,
+                    manifest.folder_id,
+                    manifest.sequence_number
                 );
-                // Log warning but continue (eventually consistent)
+                break;
             }
+
+            if previous.sequence_number < first_missing {
+                break;
+            }
+
+            cursor = previous.previous_manifest_cid.clone();
+            chain.push((cid, previous));
+        }
+
+        if chain.is_empty() {
+            log::warn!(
+                "No predecessor manifests available for folder {}, continuing without them",
+                manifest.folder_id
+            );
+            return Ok(());
         }
 
+        // Apply oldest first so deletions and re-additions land in source order
+        for (cid, previous) in chain.iter().rev() {
+            log::info!(
+                "Recovering skipped manifest {} (folder {} seq {})",
+                cid,
+                previous.folder_id,
+                previous.sequence_number
+            );
+
+            self.apply_manifest(cid, previous, multiaddr).await?;
+
+            let mut state = self.state.write().await;
+            if !state.processed_manifests.contains_key(cid) {
+                return Err(ArchivistError::SyncError(format!(
+                    "Skipped manifest {} (seq {}) failed, deferring seq {}",
+                    cid, previous.sequence_number, manifest.sequence_number
+                )));
+            }
+            state.stats.gap_manifests_recovered += 1;
+        }
+
+        self.save_state().await?;
         Ok(())
     }
 
@@ -752,6 +1161,7 @@ impl BackupDaemon {
 
         // Remove from in-progress
         state.in_progress_manifests.remove(manifest_cid);
+        let mut newly_processed = Vec::new();
 
         match (download_result, deletion_result) {
             (Ok(dl), Ok(del)) => {
@@ -767,8 +1177,10 @@ impl BackupDaemon {
                         file_count: dl.downloaded + dl.skipped_existing,
                         total_size_bytes: manifest.stats.total_size_bytes,
                         deleted_count: del.deleted,
+                        acknowledged: false,
                     },
                 );
+                newly_processed.push(manifest_cid.to_string());
 
                 // Update stats
                 state.stats.total_manifests_processed += 1;
@@ -800,7 +1212,7 @@ impl BackupDaemon {
         }
 
         drop(state);
-        self.save_state().await?;
+        self.save_processed(&newly_processed).await?;
         Ok(())
     }
 
@@ -855,7 +1267,7 @@ impl BackupDaemon {
         let all_manifests = self.discover_manifests().await?;
 
         // 2. Filter unprocessed
-        let unprocessed = self.filter_unprocessed(all_manifests).await;
+        let unprocessed = self.filter_unprocessed(all_manifests.clone()).await;
 
         if unprocessed.is_empty() {
             log::debug!("No new manifests to process");
@@ -895,7 +1307,10 @@ impl BackupDaemon {
         // 4. Retry failed manifests (if retry count < max)
         self.retry_failed_manifests().await?;
 
-        // 5. Update last poll time
+        // 5. Acknowledge processed manifests back to their source peers
+        self.acknowledge_processed(&all_manifests).await;
+
+        // 6. Update last poll time
         {
             let mut state = self.state.write().await;
             state.last_poll_time = Utc::now();
@@ -905,6 +1320,83 @@ impl BackupDaemon {
         Ok(unprocessed.len() as u32)
     }
 
+    /// Send acknowledgements for processed manifests the source hasn't accepted yet
+    ///
+    /// Failed acks are left unacknowledged and resent on the next cycle.
+    async fn acknowledge_processed(&self, discovered: &[DiscoveredManifest]) {
+        let pending: Vec<(DiscoveredManifest, ProcessedManifest)> = {
+            let state = self.state.read().await;
+            discovered
+                .iter()
+                .filter_map(|m| {
+                    state
+                        .processed_manifests
+                        .get(&m.cid)
+                        .filter(|p| !p.acknowledged)
+                        .map(|p| (m.clone(), p.clone()))
+                })
+                .collect()
+        };
+
+        if pending.is_empty() {
+            return;
+        }
+
+        let own_peer_id = match self.api_client.get_info().await {
+            Ok(info) => info.id,
+            Err(e) => {
+                log::warn!("Cannot acknowledge manifests, node info unavailable: {}", e);
+                return;
+            }
+        };
+
+        let mut acknowledged = Vec::new();
+        for (discovered, processed) in pending {
+            let ack = ManifestAck {
+                peer_id: own_peer_id.clone(),
+                folder_id: processed.folder_id.clone(),
+                manifest_cid: processed.manifest_cid.clone(),
+                sequence_number: processed.sequence_number,
+                file_count: processed.file_count,
+            };
+
+            match self
+                .manifest_client
+                .send_ack(&discovered.source_host, discovered.source_port, &ack)
+                .await
+            {
+                Ok(()) => {
+                    log::info!(
+                        "Acknowledged manifest {} to {}",
+                        ack.manifest_cid,
+                        discovered.source_host
+                    );
+                    acknowledged.push(ack.manifest_cid);
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Failed to acknowledge manifest {} to {}: {}",
+                        ack.manifest_cid,
+                        discovered.source_host,
+                        e
+                    );
+                }
+            }
+        }
+
+        let mut state = self.state.write().await;
+        for cid in &acknowledged {
+            if let Some(processed) = state.processed_manifests.get_mut(cid) {
+                processed.acknowledged = true;
+            }
+        }
+        drop(state);
+
+        if let Err(e) = self.save_processed(&acknowledged).await {
+            log::warn!("Failed to save manifest acknowledgements: {}", e);
+        }
+    }
+
     /// Retry manifests that previously failed
     async fn retry_failed_manifests(&self) -> Result<()> {
         let mut state = self.state.write().await;
@@ -933,6 +1425,21 @@ impl BackupDaemon {
 
         // Retry each
         for mut failed in to_retry {
+            // Skip manifests already applied while recovering a later manifest's sequence gap
+            if self
+                .state
+                .read()
+                .await
+                .processed_manifests
+                .contains_key(&failed.manifest_cid)
+            {
+                log::info!(
+                    "Failed manifest {} has since been processed, dropping retry",
+                    failed.manifest_cid
+                );
+                continue;
+            }
+
             log::info!(
                 "Retrying failed manifest: {} (attempt {}/{})",
                 failed.manifest_cid,
@@ -972,16 +1479,26 @@ impl BackupDaemon {
     pub async fn retry_manifest(&self, manifest_cid: &str) -> Result<()> {
         log::info!("Manual retry requested for manifest: {}", manifest_cid);
 
-        // Find and remove from failed list, capturing peer info
+        // Find and remove from failed (or quota-exceeded) list, capturing peer info
         let mut state = self.state.write().await;
         let failed_info = state
             .failed_manifests
             .iter()
             .find(|m| m.manifest_cid == manifest_cid)
-            .map(|m| (m.source_peer_id.clone(), m.multiaddr.clone()));
+            .map(|m| (m.source_peer_id.clone(), m.multiaddr.clone()))
+            .or_else(|| {
+                state
+                    .quota_exceeded_manifests
+                    .iter()
+                    .find(|m| m.manifest_cid == manifest_cid)
+                    .map(|m| (m.source_peer_id.clone(), m.multiaddr.clone()))
+            });
         state
             .failed_manifests
             .retain(|m| m.manifest_cid != manifest_cid);
+        state
+            .quota_exceeded_manifests
+            .retain(|m| m.manifest_cid != manifest_cid);
         drop(state);
 
         // Process manifest with peer info if available
@@ -1014,3 +1531,142 @@ impl BackupDaemon {
         Ok(())
     }
 }
+
+/// Whether usage exceeds either limit
+fn quota_exceeded(usage: &SourceUsage, max_bytes: Option<u64>, max_files: Option<u32>) -> bool {
+    max_bytes.is_some_and(|max| usage.bytes > max) || max_files.is_some_and(|max| usage.files > max)
+}
+
+/// Range of sequence numbers missing between the last processed manifest and a new one
+fn sequence_gap(last_seq: Option<u64>, sequence_number: u64) -> Option<(u64, u64)> {
+    let expected = last_seq? + 1;
+    if sequence_number > expected {
+        Some((expected, sequence_number - 1))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_gap() {
+        assert_eq!(sequence_gap(None, 7), None);
+        assert_eq!(sequence_gap(Some(4), 5), None);
+        assert_eq!(sequence_gap(Some(4), 4), None);
+        assert_eq!(sequence_gap(Some(4), 8), Some((5, 7)));
+    }
+
+    fn processed(
+        cid: &str,
+        peer: &str,
+        folder: &str,
+        seq: u64,
+        bytes: u64,
+        files: u32,
+    ) -> ProcessedManifest {
+        ProcessedManifest {
+            manifest_cid: cid.to_string(),
+            source_peer_id: peer.to_string(),
+            sequence_number: seq,
+            folder_id: folder.to_string(),
+            processed_at: Utc::now(),
+            file_count: files,
+            total_size_bytes: bytes,
+            deleted_count: 0,
+            acknowledged: false,
+        }
+    }
+
+    #[test]
+    fn test_source_usage_counts_latest_manifest_per_folder() {
+        let mut state = DaemonState::default();
+        for m in [
+            processed("a1", "peer-a", "photos", 1, 100, 1),
+            processed("a2", "peer-a", "photos", 2, 300, 3),
+            processed("a3", "peer-a", "docs", 1, 50, 2),
+            processed("b1", "peer-b", "photos", 1, 10, 1),
+        ] {
+            state.processed_manifests.insert(m.manifest_cid.clone(), m);
+        }
+
+        let usage = state.compute_source_usage();
+        assert_eq!(
+            usage["peer-a"],
+            SourceUsage {
+                bytes: 350,
+                files: 5
+            }
+        );
+        assert_eq!(
+            usage["peer-b"],
+            SourceUsage {
+                bytes: 10,
+                files: 1
+            }
+        );
+
+        // A new photos manifest replaces the folder's current usage
+        let projected = state.projected_source_usage("peer-a", "photos", 1000, 4);
+        assert_eq!(
+            projected,
+            SourceUsage {
+                bytes: 1050,
+                files: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_state_round_trips_through_store() {
+        let store = Store::open_in_memory().unwrap();
+        let mut state = DaemonState::default();
+        let m = processed("a1", "peer-a", "photos", 1, 100, 1);
+        state.processed_manifests.insert(m.manifest_cid.clone(), m);
+        state.stats.total_manifests_processed = 1;
+
+        // Only the listed processed manifests are written
+        BackupDaemon::write_state(&store, &state, std::iter::empty()).unwrap();
+        let loaded = BackupDaemon::load_state(&store).unwrap();
+        assert!(loaded.processed_manifests.is_empty());
+        assert_eq!(loaded.stats.total_manifests_processed, 1);
+
+        BackupDaemon::write_state(&store, &state, [&"a1".to_string()]).unwrap();
+        let loaded = BackupDaemon::load_state(&store).unwrap();
+        assert_eq!(loaded.processed_manifests["a1"].total_size_bytes, 100);
+    }
+
+    #[test]
+    fn test_quota_exceeded() {
+        let usage = SourceUsage {
+            bytes: 1050,
+            files: 6,
+        };
+        assert!(!quota_exceeded(&usage, None, None));
+        assert!(!quota_exceeded(&usage, Some(1050), Some(6)));
+        assert!(quota_exceeded(&usage, Some(1000), None));
+        assert!(quota_exceeded(&usage, None, Some(5)));
+    }
+
+    #[test]
+    fn test_manifest_without_predecessor_link() {
+        let json = r#"{
+            "version": "1.0",
+            "folder_id": "folder-1",
+            "folder_path": "/data/photos",
+            "source_peer_id": "16Uiu2HAmXYZ",
+            "sequence_number": 3,
+            "last_updated": "2025-01-01T00:00:00Z",
+            "manifest_cid": null,
+            "files": [],
+            "deleted_files": [],
+            "stats": {"total_files": 0, "total_size_bytes": 0}
+        }"#;
+
+        let manifest: ManifestFile = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.sequence_number, 3);
+        assert!(manifest.previous_manifest_cid.is_none());
+    }
+}
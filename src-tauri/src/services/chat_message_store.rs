@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
+use rusqlite::params;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
-use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use std::path::Path;
 
 use super::chat_types::{ConversationSummary, ConversationType, DeliveryStatus, StoredMessage};
 use crate::error::{ArchivistError, Result};
+use crate::storage::{self, storage_error, Store};
 
 /// A conversation (direct or group).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,57 +23,100 @@ pub struct Conversation {
     pub members: Vec<String>,
 }
 
-/// Manages conversations and messages on disk.
+/// Conversation metadata as stored in the database; messages are stored separately.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredConversation {
+    id: String,
+    conversation_type: ConversationType,
+    display_name: String,
+    unread_count: u32,
+    last_message_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    members: Vec<String>,
+}
+
+impl From<&Conversation> for StoredConversation {
+    fn from(conv: &Conversation) -> Self {
+        Self {
+            id: conv.id.clone(),
+            conversation_type: conv.conversation_type.clone(),
+            display_name: conv.display_name.clone(),
+            unread_count: conv.unread_count,
+            last_message_at: conv.last_message_at,
+            members: conv.members.clone(),
+        }
+    }
+}
+
+/// Database namespace for conversation metadata
+const CONVERSATIONS_NAMESPACE: &str = "chat.conversations";
+
+/// Manages conversations and messages in the shared database.
 pub struct MessageStore {
     conversations: HashMap<String, Conversation>,
-    base_dir: PathBuf,
+    store: Store,
 }
 
 impl MessageStore {
-    pub fn new(base_dir: &Path) -> Result<Self> {
-        let messages_dir = base_dir.join("messages");
-        std::fs::create_dir_all(&messages_dir)
-            .map_err(|e| ArchivistError::ChatError(format!("Create messages dir: {}", e)))?;
-
-        let mut conversations = HashMap::new();
-        let mut loaded_paths: Vec<PathBuf> = Vec::new();
-
-        // Load existing conversations
-        if let Ok(entries) = std::fs::read_dir(&messages_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().is_some_and(|e| e == "json") {
-                    match std::fs::read_to_string(&path) {
-                        Ok(data) => match serde_json::from_str::<Conversation>(&data) {
-                            Ok(conv) => {
-                                loaded_paths.push(path);
-                                conversations.insert(conv.id.clone(), conv);
-                            }
-                            Err(e) => {
-                                log::warn!("Failed to parse conversation {:?}: {}", path, e);
-                            }
-                        },
-                        Err(e) => {
-                            log::warn!("Failed to read conversation {:?}: {}", path, e);
+    /// Load conversations from the database. Conversations from the legacy
+    /// one-JSON-file-per-conversation layout under `base_dir/messages` are
+    /// imported on first run.
+    pub fn new(base_dir: &Path, store: Store) -> Result<Self> {
+        import_legacy_conversations(&base_dir.join("messages"), &store)?;
+
+        let mut conversations: HashMap<String, Conversation> = store
+            .list_documents::<StoredConversation>(CONVERSATIONS_NAMESPACE)?
+            .into_iter()
+            .map(|c| {
+                (
+                    c.id.clone(),
+                    Conversation {
+                        id: c.id,
+                        conversation_type: c.conversation_type,
+                        display_name: c.display_name,
+                        messages: Vec::new(),
+                        unread_count: c.unread_count,
+                        last_message_at: c.last_message_at,
+                        members: c.members,
+                    },
+                )
+            })
+            .collect();
+
+        // Messages come back in insertion order
+        store.transaction(|tx| {
+            let mut stmt = tx
+                .prepare("SELECT conversation_id, value FROM chat_messages ORDER BY rowid")
+                .map_err(storage_error)?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })
+                .map_err(storage_error)?;
+
+            for row in rows {
+                let (conversation_id, json) = row.map_err(storage_error)?;
+                match serde_json::from_str::<StoredMessage>(&json) {
+                    Ok(msg) => {
+                        if let Some(conv) = conversations.get_mut(&conversation_id) {
+                            conv.messages.push(msg);
                         }
                     }
+                    Err(e) => {
+                        log::warn!("Failed to parse message in {}: {}", conversation_id, e);
+                    }
                 }
             }
-        }
+            Ok(())
+        })?;
 
-        log::info!("Loaded {} conversations from disk", conversations.len());
+        log::info!("Loaded {} conversations from database", conversations.len());
 
-        let store = Self {
+        Ok(Self {
             conversations,
-            base_dir: base_dir.to_path_buf(),
-        };
-
-        // Migrate: re-persist with current sanitized filenames, remove stale files
-        if !loaded_paths.is_empty() {
-            store.migrate_filenames(&loaded_paths);
-        }
-
-        Ok(store)
+            store,
+        })
     }
 
     /// Get or create a direct conversation with a peer.
@@ -117,8 +162,26 @@ impl MessageStore {
             if !message.is_outgoing {
                 conv.unread_count += 1;
             }
+            let record = StoredConversation::from(&*conv);
+            let json = serde_json::to_string(&message)
+                .map_err(|e| ArchivistError::ChatError(format!("Serialize message: {}", e)))?;
+
+            self.store.transaction(|tx| {
+                tx.execute(
+                    "INSERT OR REPLACE INTO chat_messages (conversation_id, id, timestamp, value)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        conversation_id,
+                        message.id,
+                        message.timestamp.to_rfc3339(),
+                        json
+                    ],
+                )
+                .map_err(storage_error)?;
+                storage::put_document(tx, CONVERSATIONS_NAMESPACE, conversation_id, &record)
+            })?;
+
             conv.messages.push(message);
-            self.persist_conversation(conversation_id)?;
         }
         Ok(())
     }
@@ -133,8 +196,17 @@ impl MessageStore {
         if let Some(conv) = self.conversations.get_mut(conversation_id) {
             if let Some(msg) = conv.messages.iter_mut().find(|m| m.id == message_id) {
                 msg.delivery_status = status;
+                let json = serde_json::to_string(msg)
+                    .map_err(|e| ArchivistError::ChatError(format!("Serialize message: {}", e)))?;
+                self.store.transaction(|tx| {
+                    tx.execute(
+                        "UPDATE chat_messages SET value = ?1 WHERE conversation_id = ?2 AND id = ?3",
+                        params![json, conversation_id, message_id],
+                    )
+                    .map_err(storage_error)?;
+                    Ok(())
+                })?;
             }
-            self.persist_conversation(conversation_id)?;
         }
         Ok(())
     }
@@ -202,15 +274,14 @@ impl MessageStore {
     /// Delete a conversation and its messages.
     pub fn delete_conversation(&mut self, conversation_id: &str) -> Result<()> {
         self.conversations.remove(conversation_id);
-        let path = self
-            .base_dir
-            .join("messages")
-            .join(format!("{}.json", sanitize_filename(conversation_id)));
-        if path.exists() {
-            std::fs::remove_file(&path)
-                .map_err(|e| ArchivistError::ChatError(format!("Delete conversation: {}", e)))?;
-        }
-        Ok(())
+        self.store.transaction(|tx| {
+            tx.execute(
+                "DELETE FROM chat_messages WHERE conversation_id = ?1",
+                params![conversation_id],
+            )
+            .map_err(storage_error)?;
+            storage::delete_document(tx, CONVERSATIONS_NAMESPACE, conversation_id)
+        })
     }
 
     /// Get total unread count across all conversations.
@@ -262,46 +333,57 @@ impl MessageStore {
         Ok(())
     }
 
-    /// Re-persist all conversations with current sanitized filenames,
-    /// then remove stale files (from old sanitization rules).
-    fn migrate_filenames(&self, loaded_paths: &[PathBuf]) {
-        let mut expected: HashSet<String> = HashSet::new();
-        for conv_id in self.conversations.keys() {
-            let filename = format!("{}.json", sanitize_filename(conv_id));
-            expected.insert(filename);
-            if let Err(e) = self.persist_conversation(conv_id) {
-                log::warn!("Migration: failed to persist {}: {}", conv_id, e);
-            }
-        }
-
-        // Remove loaded files whose names no longer match (stale from old sanitization)
-        for path in loaded_paths {
-            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                if !expected.contains(name) {
-                    log::info!("Removing stale conversation file: {:?}", path);
-                    if let Err(e) = std::fs::remove_file(path) {
-                        log::warn!("Failed to remove stale file {:?}: {}", path, e);
-                    }
-                }
-            }
-        }
-    }
-
+    /// Save conversation metadata (not its messages).
     fn persist_conversation(&self, conversation_id: &str) -> Result<()> {
         if let Some(conv) = self.conversations.get(conversation_id) {
-            let path = self
-                .base_dir
-                .join("messages")
-                .join(format!("{}.json", sanitize_filename(conversation_id)));
-            let data = serde_json::to_string_pretty(conv)
-                .map_err(|e| ArchivistError::ChatError(format!("Serialize conversation: {}", e)))?;
-            std::fs::write(&path, data)
-                .map_err(|e| ArchivistError::ChatError(format!("Write conversation: {}", e)))?;
+            self.store.put_document(
+                CONVERSATIONS_NAMESPACE,
+                conversation_id,
+                &StoredConversation::from(conv),
+            )?;
         }
         Ok(())
     }
 }
 
+/// Import conversations saved as one JSON file each (pre-database layout).
+fn import_legacy_conversations(messages_dir: &Path, store: &Store) -> Result<()> {
+    let Ok(entries) = std::fs::read_dir(messages_dir) else {
+        return Ok(());
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(conv) = storage::take_legacy_json::<Conversation>(&path) else {
+            continue;
+        };
+
+        store.transaction(|tx| {
+            storage::put_document(
+                tx,
+                CONVERSATIONS_NAMESPACE,
+                &conv.id,
+                &StoredConversation::from(&conv),
+            )?;
+            for message in &conv.messages {
+                let json = serde_json::to_string(message)?;
+                tx.execute(
+                    "INSERT OR REPLACE INTO chat_messages (conversation_id, id, timestamp, value)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![conv.id, message.id, message.timestamp.to_rfc3339(), json],
+                )
+                .map_err(storage_error)?;
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(())
+}
+
 fn short_peer_id(peer_id: &str) -> String {
     if peer_id.len() > 12 {
         format!("{}..{}", &peer_id[..6], &peer_id[peer_id.len() - 4..])
@@ -310,6 +392,82 @@ fn short_peer_id(peer_id: &str) -> String {
     }
 }
 
-fn sanitize_filename(name: &str) -> String {
-    crate::path_utils::sanitize_filename(name)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::chat_types::ChatMessageContent;
+    use tempfile::TempDir;
+
+    fn message(id: &str, text: &str, is_outgoing: bool) -> StoredMessage {
+        StoredMessage {
+            id: id.to_string(),
+            sender_peer_id: "16Uiu2HAmPeer".to_string(),
+            content: ChatMessageContent {
+                text: text.to_string(),
+                reply_to: None,
+                attachments: Vec::new(),
+            },
+            timestamp: Utc::now(),
+            delivery_status: DeliveryStatus::Sending,
+            is_outgoing,
+        }
+    }
+
+    #[test]
+    fn test_messages_persist_in_order() {
+        let tmp = TempDir::new().unwrap();
+        let store = Store::open_in_memory().unwrap();
+
+        {
+            let mut messages = MessageStore::new(tmp.path(), store.clone()).unwrap();
+            let conv_id = messages.get_or_create_direct("16Uiu2HAmPeer").id.clone();
+            messages
+                .add_message(&conv_id, message("m1", "hello", false))
+                .unwrap();
+            messages
+                .add_message(&conv_id, message("m2", "world", true))
+                .unwrap();
+            messages
+                .update_delivery_status(&conv_id, "m2", DeliveryStatus::Delivered)
+                .unwrap();
+        }
+
+        let messages = MessageStore::new(tmp.path(), store).unwrap();
+        let loaded = messages.get_messages("dm:16Uiu2HAmPeer", 10, None);
+        let ids: Vec<&str> = loaded.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["m1", "m2"]);
+        assert_eq!(loaded[1].delivery_status, DeliveryStatus::Delivered);
+        assert_eq!(messages.total_unread(), 1);
+    }
+
+    #[test]
+    fn test_legacy_conversation_files_imported() {
+        let tmp = TempDir::new().unwrap();
+        let messages_dir = tmp.path().join("messages");
+        std::fs::create_dir_all(&messages_dir).unwrap();
+
+        let legacy = Conversation {
+            id: "group-1".to_string(),
+            conversation_type: ConversationType::Group,
+            display_name: "Friends".to_string(),
+            messages: vec![message("m1", "hi all", false)],
+            unread_count: 1,
+            last_message_at: Some(Utc::now()),
+            members: vec!["a".to_string(), "b".to_string()],
+        };
+        std::fs::write(
+            messages_dir.join("group-1.json"),
+            serde_json::to_string(&legacy).unwrap(),
+        )
+        .unwrap();
+
+        let store = Store::open_in_memory().unwrap();
+        let mut messages = MessageStore::new(tmp.path(), store.clone()).unwrap();
+        assert_eq!(messages.get_messages("group-1", 10, None).len(), 1);
+        assert!(!messages_dir.join("group-1.json").exists());
+
+        messages.delete_conversation("group-1").unwrap();
+        let messages = MessageStore::new(tmp.path(), store).unwrap();
+        assert_eq!(messages.conversation_count(), 0);
+    }
 }
@@ -51,12 +51,13 @@ impl ChatService {
         peer_id: String,
         cert_fingerprint: String,
         chat_settings: &crate::services::config::ChatSettings,
+        store: crate::storage::Store,
     ) -> Result<Self> {
         let identity = IdentityManager::load_or_create(&key_store, &peer_id)?;
         let sessions = SessionManager::new();
         let group_sessions = GroupSessionManager::new();
         let tofu_store = TofuStore::new(key_store.base_dir())?;
-        let message_store = MessageStore::new(key_store.base_dir())?;
+        let message_store = MessageStore::new(key_store.base_dir(), store)?;
         let delivery_queue = DeliveryQueue::new();
 
         Ok(Self {
@@ -350,6 +350,21 @@ pub struct NodeSettings {
     /// Optional external IP address. When set, the node uses --nat=extip:<ip> instead of --nat=upnp.
     #[serde(default)]
     pub announce_ip: Option<String>,
+    /// Mock node settings for offline demos
+    #[serde(default)]
+    pub simulation: SimulationSettings,
+}
+
+/// Offline simulation mode: run an in-process mock node instead of the sidecar
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimulationSettings {
+    pub enabled: bool,
+    /// Delay added to every mock node API response
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Fraction of mock node API requests (0.0-1.0) that fail with HTTP 503
+    #[serde(default)]
+    pub failure_rate: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -368,6 +383,13 @@ pub struct SyncSettings {
     /// Port for the backup server's HTTP trigger endpoint (default: 8086)
     #[serde(default = "default_trigger_port")]
     pub backup_trigger_port: u16,
+    /// Additional backup peers notified alongside `backup_peer_address`
+    #[serde(default)]
+    pub backup_peers: Vec<BackupPeerConfig>,
+    /// Number of backup peers that must acknowledge a manifest before the
+    /// folder is considered fully replicated
+    #[serde(default = "default_desired_backup_replicas")]
+    pub desired_backup_replicas: u32,
 
     // NEW: Continuous sync settings
     pub manifest_update_threshold: u32,
@@ -375,6 +397,51 @@ pub struct SyncSettings {
     pub manifest_max_retries: u32,
 }
 
+impl SyncSettings {
+    /// All enabled backup peers, including the legacy single `backup_peer_address`
+    pub fn enabled_backup_peers(&self) -> Vec<BackupPeerConfig> {
+        let mut peers: Vec<BackupPeerConfig> = Vec::new();
+        if let Some(ref address) = self.backup_peer_address {
+            if !address.is_empty() {
+                peers.push(BackupPeerConfig {
+                    nickname: self
+                        .backup_peer_nickname
+                        .clone()
+                        .unwrap_or_else(|| "Backup peer".to_string()),
+                    address: address.clone(),
+                    trigger_port: self.backup_trigger_port,
+                    enabled: true,
+                });
+            }
+        }
+        for peer in &self.backup_peers {
+            if peer.enabled && !peers.iter().any(|p| p.address == peer.address) {
+                peers.push(peer.clone());
+            }
+        }
+        peers
+    }
+}
+
+/// A backup peer that receives manifest notifications from this node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupPeerConfig {
+    /// Human-friendly name for this peer
+    pub nickname: String,
+    /// Multiaddr of the backup peer (e.g., /ip4/1.2.3.4/tcp/8070/p2p/...)
+    pub address: String,
+    /// Port of the backup server's trigger endpoint (default: 8086)
+    #[serde(default = "default_trigger_port")]
+    pub trigger_port: u16,
+    /// Whether this peer is notified about new manifests
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_desired_backup_replicas() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationSettings {
     pub sound_enabled: bool,
@@ -411,12 +478,29 @@ pub struct BackupServerSettings {
     /// Source peers to poll for manifests (list of host:port pairs)
     #[serde(default)]
     pub source_peers: Vec<SourcePeerConfig>,
+    /// Hours between backup content audits (0 = disabled)
+    #[serde(default = "default_audit_interval_hours")]
+    pub audit_interval_hours: u64,
+    /// Files checked per scheduled audit, chosen at random (0 = check every file)
+    #[serde(default = "default_audit_sample_size")]
+    pub audit_sample_size: u32,
+    /// Re-download local files during audits to verify their content
+    #[serde(default)]
+    pub audit_verify_content: bool,
 }
 
 fn default_trigger_port() -> u16 {
     8086
 }
 
+fn default_audit_interval_hours() -> u64 {
+    24
+}
+
+fn default_audit_sample_size() -> u32 {
+    100
+}
+
 /// Configuration for a source peer to poll for manifests
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourcePeerConfig {
@@ -432,6 +516,12 @@ pub struct SourcePeerConfig {
     pub multiaddr: Option<String>,
     /// Whether this source is enabled
     pub enabled: bool,
+    /// Maximum bytes stored for this source across all its folders (None = unlimited)
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    /// Maximum number of files stored for this source (None = unlimited)
+    #[serde(default)]
+    pub max_files: Option<u32>,
 }
 
 /// Settings for the manifest discovery server (Machine A exposes this)
@@ -688,6 +778,7 @@ impl Default for AppConfig {
                 auto_start: true,
                 log_level: "DEBUG".to_string(), // Good balance of verbosity for debugging
                 announce_ip: None,
+                simulation: SimulationSettings::default(),
             },
             sync: SyncSettings {
                 auto_sync: true,
@@ -705,6 +796,8 @@ impl Default for AppConfig {
                 backup_manifest_enabled: true,
                 backup_auto_notify: false,
                 backup_trigger_port: 8086,
+                backup_peers: Vec::new(),
+                desired_backup_replicas: 1,
                 manifest_update_threshold: 1,
                 manifest_retry_interval_secs: 300,
                 manifest_max_retries: 5,
@@ -729,6 +822,9 @@ impl Default for AppConfig {
                 auto_delete_tombstones: true,
                 trigger_port: 8086,
                 source_peers: Vec::new(),
+                audit_interval_hours: 24,
+                audit_sample_size: 100,
+                audit_verify_content: false,
             },
             manifest_server: ManifestServerSettings::default(),
             media_download: MediaDownloadSettings::default(),
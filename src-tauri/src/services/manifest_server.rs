@@ -4,9 +4,13 @@
 //! This allows Machine B to query Machine A for manifest information, then fetch
 //! the actual data over the P2P network.
 //!
+//! Backup peers also report back through `POST /ack` once they have processed a
+//! manifest, which feeds the source's replication tracking.
+//!
 //! Security: Only whitelisted IPs can access this endpoint.
 
 use crate::error::{ArchivistError, Result};
+use crate::services::replication::{ManifestAck, ReplicationTracker};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::net::IpAddr;
@@ -115,6 +119,7 @@ impl Default for ManifestServerConfig {
 pub struct ManifestServer {
     registry: Arc<RwLock<ManifestRegistry>>,
     config: Arc<RwLock<ManifestServerConfig>>,
+    replication: Option<Arc<RwLock<ReplicationTracker>>>,
     shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
 }
 
@@ -124,6 +129,7 @@ impl ManifestServer {
         Self {
             registry,
             config: Arc::new(RwLock::new(ManifestServerConfig::default())),
+            replication: None,
             shutdown_tx: None,
         }
     }
@@ -136,10 +142,17 @@ impl ManifestServer {
         Self {
             registry,
             config: Arc::new(RwLock::new(config)),
+            replication: None,
             shutdown_tx: None,
         }
     }
 
+    /// Record backup peer acknowledgements received on `POST /ack` in the given tracker
+    pub fn with_replication(mut self, replication: Arc<RwLock<ReplicationTracker>>) -> Self {
+        self.replication = Some(replication);
+        self
+    }
+
     /// Update server configuration
     #[allow(dead_code)]
     pub async fn update_config(&self, config: ManifestServerConfig) {
@@ -198,6 +211,7 @@ impl ManifestServer {
         drop(config);
 
         let registry = self.registry.clone();
+        let replication = self.replication.clone();
         let config_for_filter = self.config.clone();
 
         // Create IP whitelist filter
@@ -233,12 +247,22 @@ impl ManifestServer {
             .and(warp::any().map(move || registry.clone()))
             .and_then(handle_get_manifests);
 
+        // POST /ack - Backup peer acknowledges a processed manifest
+        let ack_route = warp::path("ack")
+            .and(warp::post())
+            .and(ip_filter.clone())
+            .and(warp::body::content_length_limit(16 * 1024))
+            .and(warp::body::json())
+            .and(warp::any().map(move || replication.clone()))
+            .and_then(handle_ack);
+
         // Health check (no auth required)
         let health_route = warp::path("health")
             .and(warp::get())
             .map(|| warp::reply::json(&serde_json::json!({"status": "ok"})));
 
         let routes = manifests_route
+            .or(ack_route)
             .or(health_route)
             .recover(handle_rejection)
             .with(warp::log("manifest_server"));
@@ -313,6 +337,44 @@ async fn handle_get_manifests(
     Ok(warp::reply::json(&response))
 }
 
+async fn handle_ack(
+    ack: ManifestAck,
+    replication: Option<Arc<RwLock<ReplicationTracker>>>,
+) -> std::result::Result<impl warp::Reply, warp::Rejection> {
+    log::info!(
+        "Backup peer {} acknowledged manifest {} (folder {}, seq {})",
+        ack.peer_id,
+        ack.manifest_cid,
+        ack.folder_id,
+        ack.sequence_number
+    );
+
+    let Some(replication) = replication else {
+        return Ok(warp::reply::with_status(
+            warp::reply::json(&serde_json::json!({"status": "ignored"})),
+            warp::http::StatusCode::ACCEPTED,
+        ));
+    };
+
+    let mut tracker = replication.write().await;
+    match tracker.record_ack(&ack) {
+        Ok(()) => Ok(warp::reply::with_status(
+            warp::reply::json(&serde_json::json!({"status": "recorded"})),
+            warp::http::StatusCode::OK,
+        )),
+        Err(e) => {
+            log::error!("Failed to record manifest acknowledgement: {}", e);
+            Ok(warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({
+                    "error": "Internal Server Error",
+                    "message": e.to_string()
+                })),
+                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+            ))
+        }
+    }
+}
+
 /// Client for querying a remote manifest server
 pub struct ManifestClient {
     client: reqwest::Client,
@@ -357,6 +419,25 @@ impl ManifestClient {
                 ArchivistError::ApiError(format!("Failed to parse manifest response: {}", e))
             })
     }
+
+    /// Acknowledge a processed manifest to the source peer's manifest server
+    pub async fn send_ack(&self, host: &str, port: u16, ack: &ManifestAck) -> Result<()> {
+        let url = format!("http://{}:{}/ack", host, port);
+
+        let response =
+            self.client.post(&url).json(ack).send().await.map_err(|e| {
+                ArchivistError::ApiError(format!("Failed to send manifest ack: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            return Err(ArchivistError::ApiError(format!(
+                "Manifest server rejected ack: HTTP {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for ManifestClient {
@@ -0,0 +1,681 @@
+//! In-process mock archivist node for offline simulation
+//!
+//! Implements the subset of `/api/archivist/v1` used by `NodeApiClient` (data,
+//! manifests, peers, space, spr, sales, purchases, availability) on top of
+//! in-memory state. `NodeService` runs it in place of the sidecar when simulation
+//! mode is enabled, and integration tests start it directly on an ephemeral port.
+//!
+//! Every request passes through a fault filter that adds the configured latency
+//! and fails a configurable fraction of requests with `503 Service Unavailable`.
+
+use crate::error::{ArchivistError, Result};
+use crate::node_api::{
+    ArchivistInfo, Availability, AvailabilityRequest, DataItem, DataListResponse, ManifestInfo,
+    NodeInfo, PeerInfo, Purchase, SpaceInfo, StorageAsk, StorageContent, StorageRequest,
+    StorageRequestParams,
+};
+use crate::services::config::SimulationSettings;
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use warp::http::StatusCode;
+use warp::reply::Response;
+use warp::{Filter, Reply};
+
+/// Block size used to report `totalBlocks` in `/space`
+const MOCK_BLOCK_SIZE: u64 = 64 * 1024;
+
+/// Behaviour knobs for the mock node
+#[derive(Debug, Clone)]
+pub struct MockNodeOptions {
+    /// Delay added before every response
+    pub latency: Duration,
+    /// Fraction of requests (0.0-1.0) answered with HTTP 503
+    pub failure_rate: f64,
+    /// Storage quota; uploads beyond it fail like a full sidecar repo
+    pub quota_bytes: u64,
+    /// Peer ID reported by `/debug/info`
+    pub peer_id: String,
+}
+
+impl Default for MockNodeOptions {
+    fn default() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            failure_rate: 0.0,
+            quota_bytes: 10 * 1024 * 1024 * 1024,
+            peer_id: "16Uiu2HAmSimulatedArchivistNode".to_string(),
+        }
+    }
+}
+
+impl MockNodeOptions {
+    /// Build options from the persisted simulation settings and node storage quota
+    pub fn from_settings(settings: &SimulationSettings, quota_bytes: u64) -> Self {
+        Self {
+            latency: Duration::from_millis(settings.latency_ms),
+            failure_rate: settings.failure_rate.clamp(0.0, 1.0),
+            quota_bytes,
+            ..Self::default()
+        }
+    }
+}
+
+struct StoredContent {
+    data: Bytes,
+    filename: Option<String>,
+    mimetype: Option<String>,
+}
+
+#[derive(Default)]
+struct MockNodeState {
+    content: BTreeMap<String, StoredContent>,
+    peers: Vec<PeerInfo>,
+    availability: Option<Availability>,
+    purchases: BTreeMap<String, Purchase>,
+}
+
+impl MockNodeState {
+    fn used_bytes(&self) -> u64 {
+        self.content.values().map(|c| c.data.len() as u64).sum()
+    }
+}
+
+type SharedOptions = Arc<RwLock<MockNodeOptions>>;
+type SharedState = Arc<RwLock<MockNodeState>>;
+type BoxedRoute = warp::filters::BoxedFilter<(Response,)>;
+
+/// A running mock node. Stops serving when dropped.
+pub struct MockNode {
+    addr: SocketAddr,
+    options: SharedOptions,
+    shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+impl MockNode {
+    /// Start serving on `127.0.0.1:<port>` (0 picks a free port)
+    pub async fn start(port: u16, options: MockNodeOptions) -> Result<Self> {
+        let options = Arc::new(RwLock::new(options));
+        let state: SharedState = Arc::new(RwLock::new(MockNodeState::default()));
+
+        let routes = fault_filter(options.clone())
+            .and(api_routes(options.clone(), state))
+            .recover(handle_rejection);
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let (addr, server) = warp::serve(routes)
+            .try_bind_with_graceful_shutdown(([127, 0, 0, 1], port), async {
+                rx.await.ok();
+            })
+            .map_err(|e| {
+                ArchivistError::NodeStartFailed(format!(
+                    "Failed to start mock node on port {}: {}",
+                    port, e
+                ))
+            })?;
+
+        tokio::spawn(server);
+        log::info!("Mock archivist node listening on {}", addr);
+
+        Ok(Self {
+            addr,
+            options,
+            shutdown_tx: Some(tx),
+        })
+    }
+
+    /// Port the mock API is bound to
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// Current latency and failure injection settings
+    pub async fn options(&self) -> MockNodeOptions {
+        self.options.read().await.clone()
+    }
+
+    /// Change latency and failure injection without restarting
+    pub async fn set_options(&self, options: MockNodeOptions) {
+        *self.options.write().await = options;
+    }
+
+    /// Stop serving
+    pub fn stop(&mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+            log::info!("Mock archivist node stopped");
+        }
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// Rejection used by the fault filter
+#[derive(Debug)]
+struct InjectedFailure;
+impl warp::reject::Reject for InjectedFailure {}
+
+/// Apply configured latency, then fail a fraction of requests
+fn fault_filter(
+    options: SharedOptions,
+) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    warp::any()
+        .map(move || options.clone())
+        .and_then(|options: SharedOptions| async move {
+            let (latency, failure_rate) = {
+                let opts = options.read().await;
+                (opts.latency, opts.failure_rate)
+            };
+            if !latency.is_zero() {
+                tokio::time::sleep(latency).await;
+            }
+            if failure_rate > 0.0 && rand::random::<f64>() < failure_rate {
+                return Err(warp::reject::custom(InjectedFailure));
+            }
+            Ok(())
+        })
+        .untuple_one()
+}
+
+fn api_routes(options: SharedOptions, state: SharedState) -> BoxedRoute {
+    let with_options = warp::any().map(move || options.clone());
+    let with_state = warp::any().map(move || state.clone());
+
+    let info = warp::path!("api" / "archivist" / "v1" / "debug" / "info")
+        .and(warp::get())
+        .and(with_options.clone())
+        .and_then(handle_info);
+
+    let log_level = warp::path!("api" / "archivist" / "v1" / "debug" / "chronicles" / "loglevel")
+        .and(warp::post())
+        .map(|| StatusCode::OK.into_response());
+
+    let list_data = warp::path!("api" / "archivist" / "v1" / "data")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(handle_list_data);
+
+    let upload = warp::path!("api" / "archivist" / "v1" / "data")
+        .and(warp::post())
+        .and(warp::header::optional::<String>("content-type"))
+        .and(warp::header::optional::<String>("content-disposition"))
+        .and(warp::body::bytes())
+        .and(with_options.clone())
+        .and(with_state.clone())
+        .and_then(handle_upload);
+
+    let download = warp::path!("api" / "archivist" / "v1" / "data" / String)
+        .and(warp::get())
+        .and(warp::header::optional::<String>("range"))
+        .and(with_state.clone())
+        .and_then(handle_download);
+
+    let delete = warp::path!("api" / "archivist" / "v1" / "data" / String)
+        .and(warp::delete())
+        .and(with_state.clone())
+        .and_then(handle_delete);
+
+    let network_download = warp::path!("api" / "archivist" / "v1" / "data" / String / "network")
+        .and(warp::post())
+        .and(with_state.clone())
+        .and_then(handle_network_download);
+
+    let spr = warp::path!("api" / "archivist" / "v1" / "spr")
+        .and(warp::get())
+        .and(with_options.clone())
+        .and_then(handle_spr);
+
+    let peers = warp::path!("api" / "archivist" / "v1" / "peers")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(handle_peers);
+
+    let connect = warp::path!("api" / "archivist" / "v1" / "connect" / String)
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_state.clone())
+        .and_then(handle_connect);
+
+    let space = warp::path!("api" / "archivist" / "v1" / "space")
+        .and(warp::get())
+        .and(with_options.clone())
+        .and(with_state.clone())
+        .and_then(handle_space);
+
+    let sales_slots = warp::path!("api" / "archivist" / "v1" / "sales" / "slots")
+        .and(warp::get())
+        .map(|| warp::reply::json(&Vec::<serde_json::Value>::new()).into_response());
+
+    let sales_slot = warp::path!("api" / "archivist" / "v1" / "sales" / "slots" / String)
+        .and(warp::get())
+        .map(|_slot_id: String| not_found("Slot not found"));
+
+    let get_availability = warp::path!("api" / "archivist" / "v1" / "sales" / "availability")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(handle_get_availability);
+
+    let post_availability = warp::path!("api" / "archivist" / "v1" / "sales" / "availability")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_state.clone())
+        .and_then(handle_post_availability);
+
+    let storage_request = warp::path!("api" / "archivist" / "v1" / "storage" / "request" / String)
+        .and(warp::post())
+        .and(warp::body::bytes())
+        .and(with_state.clone())
+        .and_then(handle_storage_request);
+
+    let purchases = warp::path!("api" / "archivist" / "v1" / "storage" / "purchases")
+        .and(warp::get())
+        .and(with_state.clone())
+        .and_then(handle_purchases);
+
+    let purchase = warp::path!("api" / "archivist" / "v1" / "storage" / "purchases" / String)
+        .and(warp::get())
+        .and(with_state)
+        .and_then(handle_purchase);
+
+    // Boxing each route keeps the combined filter type small enough to compile quickly
+    let routes: Vec<BoxedRoute> = vec![
+        info.boxed(),
+        log_level.boxed(),
+        list_data.boxed(),
+        upload.boxed(),
+        download.boxed(),
+        delete.boxed(),
+        network_download.boxed(),
+        spr.boxed(),
+        peers.boxed(),
+        connect.boxed(),
+        space.boxed(),
+        sales_slots.boxed(),
+        sales_slot.boxed(),
+        get_availability.boxed(),
+        post_availability.boxed(),
+        storage_request.boxed(),
+        purchases.boxed(),
+        purchase.boxed(),
+    ];
+    routes
+        .into_iter()
+        .reduce(|combined, route| combined.or(route).unify().boxed())
+        .expect("route list is not empty")
+}
+
+type HandlerResult = std::result::Result<Response, warp::Rejection>;
+
+fn not_found(message: &str) -> Response {
+    warp::reply::with_status(message.to_string(), StatusCode::NOT_FOUND).into_response()
+}
+
+/// Content ID for uploaded data: identical content always gets the same CID
+pub fn mock_cid(data: &[u8]) -> String {
+    format!(
+        "zDvZRwzm{}",
+        bs58::encode(Sha256::digest(data)).into_string()
+    )
+}
+
+/// Extract the filename from a `Content-Disposition: attachment; filename="..."` header
+fn parse_disposition_filename(value: &str) -> Option<String> {
+    let (_, rest) = value.split_once("filename=")?;
+    let name = rest.split(';').next()?.trim().trim_matches('"');
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Parse the start offset of a `Range: bytes=<start>-` header
+fn parse_range_start(value: &str) -> Option<u64> {
+    let spec = value.strip_prefix("bytes=")?;
+    spec.split_once('-')?.0.trim().parse().ok()
+}
+
+async fn handle_info(options: SharedOptions) -> HandlerResult {
+    let opts = options.read().await;
+    let addr = "/ip4/127.0.0.1/tcp/8070".to_string();
+    let info = NodeInfo {
+        id: opts.peer_id.clone(),
+        addrs: vec![addr.clone()],
+        repo: None,
+        spr: Some(format!("spr:simulated-{}", opts.peer_id)),
+        announce_addresses: vec![addr],
+        eth_address: None,
+        archivist: Some(ArchivistInfo {
+            version: "simulated".to_string(),
+            revision: None,
+            contracts: None,
+        }),
+    };
+    Ok(warp::reply::json(&info).into_response())
+}
+
+async fn handle_list_data(state: SharedState) -> HandlerResult {
+    let state = state.read().await;
+    let content = state
+        .content
+        .iter()
+        .map(|(cid, stored)| DataItem {
+            cid: cid.clone(),
+            manifest: Some(ManifestInfo {
+                filename: stored.filename.clone(),
+                mimetype: stored.mimetype.clone(),
+                dataset_size: Some(stored.data.len() as u64),
+                protected: Some(false),
+                verifiable: Some(false),
+            }),
+        })
+        .collect();
+    Ok(warp::reply::json(&DataListResponse { content }).into_response())
+}
+
+async fn handle_upload(
+    content_type: Option<String>,
+    content_disposition: Option<String>,
+    body: Bytes,
+    options: SharedOptions,
+    state: SharedState,
+) -> HandlerResult {
+    let quota = options.read().await.quota_bytes;
+    let mut state = state.write().await;
+
+    let cid = mock_cid(&body);
+    if !state.content.contains_key(&cid) && state.used_bytes() + body.len() as u64 > quota {
+        // Same wording as the sidecar so NodeApiClient maps it to a quota error
+        return Ok(warp::reply::with_status(
+            "Unable to store block".to_string(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .into_response());
+    }
+
+    log::debug!("Mock node stored {} bytes as {}", body.len(), cid);
+    state.content.insert(
+        cid.clone(),
+        StoredContent {
+            data: body,
+            filename: content_disposition
+                .as_deref()
+                .and_then(parse_disposition_filename),
+            mimetype: content_type,
+        },
+    );
+    Ok(cid.into_response())
+}
+
+async fn handle_download(cid: String, range: Option<String>, state: SharedState) -> HandlerResult {
+    let state = state.read().await;
+    let Some(stored) = state.content.get(&cid) else {
+        return Ok(not_found("Content not found"));
+    };
+
+    let total = stored.data.len() as u64;
+    let mimetype = stored
+        .mimetype
+        .clone()
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+
+    let Some(start) = range.as_deref().and_then(parse_range_start) else {
+        return Ok(
+            warp::reply::with_header(stored.data.to_vec(), "content-type", mimetype)
+                .into_response(),
+        );
+    };
+
+    if start >= total {
+        return Ok(warp::reply::with_header(
+            warp::reply::with_status(Vec::new(), StatusCode::RANGE_NOT_SATISFIABLE),
+            "content-range",
+            format!("bytes */{}", total),
+        )
+        .into_response());
+    }
+
+    let body = stored.data.slice(start as usize..).to_vec();
+    let reply = warp::reply::with_status(body, StatusCode::PARTIAL_CONTENT);
+    let reply = warp::reply::with_header(reply, "content-type", mimetype);
+    let reply = warp::reply::with_header(
+        reply,
+        "content-range",
+        format!("bytes {}-{}/{}", start, total - 1, total),
+    );
+    Ok(reply.into_response())
+}
+
+async fn handle_delete(cid: String, state: SharedState) -> HandlerResult {
+    state.write().await.content.remove(&cid);
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn handle_network_download(cid: String, state: SharedState) -> HandlerResult {
+    // There is no network in simulation mode: only content already stored can be "fetched"
+    if state.read().await.content.contains_key(&cid) {
+        Ok(StatusCode::OK.into_response())
+    } else {
+        Ok(not_found("Content not found on the simulated network"))
+    }
+}
+
+async fn handle_spr(options: SharedOptions) -> HandlerResult {
+    let peer_id = options.read().await.peer_id.clone();
+    Ok(format!("spr:simulated-{}", peer_id).into_response())
+}
+
+async fn handle_peers(state: SharedState) -> HandlerResult {
+    Ok(warp::reply::json(&state.read().await.peers).into_response())
+}
+
+async fn handle_connect(
+    peer_id: String,
+    query: HashMap<String, String>,
+    state: SharedState,
+) -> HandlerResult {
+    let mut state = state.write().await;
+    if !state.peers.iter().any(|p| p.peer_id == peer_id) {
+        state.peers.push(PeerInfo {
+            peer_id,
+            addresses: query.get("addrs").cloned().into_iter().collect(),
+        });
+    }
+    Ok("Successfully connected to peer".into_response())
+}
+
+async fn handle_space(options: SharedOptions, state: SharedState) -> HandlerResult {
+    let quota = options.read().await.quota_bytes;
+    let used = state.read().await.used_bytes();
+    let space = SpaceInfo {
+        total_blocks: used.div_ceil(MOCK_BLOCK_SIZE),
+        quota_max_bytes: quota,
+        quota_used_bytes: used,
+        quota_reserved_bytes: 0,
+    };
+    Ok(warp::reply::json(&space).into_response())
+}
+
+async fn handle_get_availability(state: SharedState) -> HandlerResult {
+    match &state.read().await.availability {
+        Some(availability) => Ok(warp::reply::json(availability).into_response()),
+        None => Ok(not_found("No availability configured")),
+    }
+}
+
+async fn handle_post_availability(
+    request: AvailabilityRequest,
+    state: SharedState,
+) -> HandlerResult {
+    let availability = Availability {
+        maximum_duration: request.maximum_duration,
+        minimum_price_per_byte_per_second: request.minimum_price_per_byte_per_second,
+        maximum_collateral_per_byte: request.maximum_collateral_per_byte,
+        available_until: request.available_until,
+    };
+    state.write().await.availability = Some(availability.clone());
+    Ok(
+        warp::reply::with_status(warp::reply::json(&availability), StatusCode::CREATED)
+            .into_response(),
+    )
+}
+
+async fn handle_storage_request(cid: String, body: Bytes, state: SharedState) -> HandlerResult {
+    let mut state = state.write().await;
+    let Some(size) = state.content.get(&cid).map(|c| c.data.len() as u64) else {
+        return Ok(not_found("Content not found"));
+    };
+
+    // The simple request_storage call sends no body; use a single-slot default
+    let params: Option<StorageRequestParams> = if body.is_empty() {
+        None
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(params) => Some(params),
+            Err(e) => {
+                return Ok(warp::reply::with_status(
+                    format!("Invalid storage request: {}", e),
+                    StatusCode::BAD_REQUEST,
+                )
+                .into_response());
+            }
+        }
+    };
+
+    let (slots, tolerance) = params
+        .as_ref()
+        .map(|p| (p.nodes + p.tolerance, p.tolerance))
+        .unwrap_or((1, 0));
+    let nonce = uuid::Uuid::new_v4();
+    let request_id = format!(
+        "0x{}",
+        hex::encode(Sha256::digest(format!("{}:{}", cid, nonce)))
+    );
+
+    let request = StorageRequest {
+        client: "0x0000000000000000000000000000000000000000".to_string(),
+        ask: StorageAsk {
+            slots,
+            slot_size: size.div_ceil(slots.max(1)),
+            duration: params
+                .as_ref()
+                .and_then(|p| p.duration.parse().ok())
+                .unwrap_or(86_400),
+            proof_probability: params
+                .as_ref()
+                .map(|p| p.proof_probability.clone())
+                .unwrap_or_default(),
+            price_per_byte_per_second: params
+                .as_ref()
+                .map(|p| p.price_per_byte_per_second.clone())
+                .unwrap_or_default(),
+            collateral_per_byte: params
+                .as_ref()
+                .map(|p| p.collateral_per_byte.clone())
+                .unwrap_or_default(),
+            max_slot_loss: tolerance,
+        },
+        content: StorageContent {
+            cid: cid.clone(),
+            merkle_root: String::new(),
+        },
+        expiry: params.as_ref().map(|p| p.expiry).unwrap_or(0),
+        nonce: nonce.simple().to_string(),
+    };
+
+    // Simulated purchases are fulfilled immediately
+    state.purchases.insert(
+        request_id.clone(),
+        Purchase {
+            state: "started".to_string(),
+            error: None,
+            request: Some(request),
+            request_id: request_id.clone(),
+        },
+    );
+    log::debug!(
+        "Mock node created storage request {} for {}",
+        request_id,
+        cid
+    );
+    Ok(request_id.into_response())
+}
+
+async fn handle_purchases(state: SharedState) -> HandlerResult {
+    let ids: Vec<String> = state.read().await.purchases.keys().cloned().collect();
+    Ok(warp::reply::json(&ids).into_response())
+}
+
+async fn handle_purchase(id: String, state: SharedState) -> HandlerResult {
+    match state.read().await.purchases.get(&id) {
+        Some(purchase) => Ok(warp::reply::json(purchase).into_response()),
+        None => Ok(not_found("Purchase not found")),
+    }
+}
+
+async fn handle_rejection(
+    err: warp::Rejection,
+) -> std::result::Result<impl warp::Reply, std::convert::Infallible> {
+    let (status, message) = if err.find::<InjectedFailure>().is_some() {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Simulated failure injected by mock node",
+        )
+    } else if err.is_not_found() {
+        (StatusCode::NOT_FOUND, "Not found")
+    } else if err.find::<warp::reject::MethodNotAllowed>().is_some() {
+        (StatusCode::METHOD_NOT_ALLOWED, "Method not allowed")
+    } else {
+        (StatusCode::BAD_REQUEST, "Bad request")
+    };
+    Ok(warp::reply::with_status(message.to_string(), status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_cid_is_content_addressed() {
+        assert_eq!(mock_cid(b"hello"), mock_cid(b"hello"));
+        assert_ne!(mock_cid(b"hello"), mock_cid(b"world"));
+        assert!(mock_cid(b"hello").starts_with("zDvZRwzm"));
+    }
+
+    #[test]
+    fn test_parse_disposition_filename() {
+        assert_eq!(
+            parse_disposition_filename("attachment; filename=\"notes.txt\""),
+            Some("notes.txt".to_string())
+        );
+        assert_eq!(parse_disposition_filename("attachment"), None);
+        assert_eq!(
+            parse_disposition_filename("attachment; filename=\"\""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_range_start() {
+        assert_eq!(parse_range_start("bytes=100-"), Some(100));
+        assert_eq!(parse_range_start("bytes=0-99"), Some(0));
+        assert_eq!(parse_range_start("items=1-"), None);
+    }
+
+    #[test]
+    fn test_options_from_settings_clamps_failure_rate() {
+        let settings = SimulationSettings {
+            enabled: true,
+            latency_ms: 250,
+            failure_rate: 1.5,
+        };
+        let options = MockNodeOptions::from_settings(&settings, 1024);
+        assert_eq!(options.latency, Duration::from_millis(250));
+        assert_eq!(options.failure_rate, 1.0);
+        assert_eq!(options.quota_bytes, 1024);
+    }
+}
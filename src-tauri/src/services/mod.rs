@@ -2,6 +2,7 @@
 
 pub mod archive_viewer;
 pub mod backup;
+pub mod backup_audit;
 pub mod backup_daemon;
 pub mod binary_manager;
 pub mod chat_delivery_queue;
@@ -19,14 +20,17 @@ pub mod irc;
 pub mod manifest_server;
 pub mod media_download;
 pub mod media_streaming;
+pub mod mock_node;
 pub mod node;
 pub mod peers;
+pub mod replication;
 pub mod sync;
 pub mod torrent;
 pub mod web_archive;
 
 pub use archive_viewer::ArchiveViewerServer;
 pub use backup::BackupService;
+pub use backup_audit::BackupAuditService;
 pub use backup_daemon::BackupDaemon;
 pub use chat_server::ChatServer;
 pub use chat_service::ChatService;
@@ -38,6 +42,7 @@ pub use media_download::MediaDownloadService;
 pub use media_streaming::{MediaStreamingConfig, MediaStreamingServer};
 pub use node::NodeService;
 pub use peers::PeerService;
+pub use replication::ReplicationTracker;
 pub use sync::SyncService;
 #[allow(unused_imports)]
 pub use torrent::TorrentService;
@@ -1,4 +1,6 @@
 use crate::error::{ArchivistError, Result};
+use crate::services::config::SimulationSettings;
+use crate::services::mock_node::{MockNode, MockNodeOptions};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -40,6 +42,8 @@ pub struct NodeStatus {
     pub spr: Option<String>,
     pub addresses: Vec<String>,
     pub public_ip: Option<String>,
+    /// True when the API is served by the in-process mock node
+    pub simulated: bool,
 }
 
 impl Default for NodeStatus {
@@ -59,6 +63,7 @@ impl Default for NodeStatus {
             spr: None,
             addresses: Vec::new(),
             public_ip: None,
+            simulated: false,
         }
     }
 }
@@ -94,6 +99,9 @@ pub struct NodeConfig {
     /// Forwarded to the sidecar as one `--bootstrap-node=<spr>` flag per entry.
     #[serde(skip)]
     pub bootstrap_nodes: Vec<String>,
+    /// When enabled, `start` serves a mock node API instead of spawning the sidecar
+    #[serde(default)]
+    pub simulation: SimulationSettings,
 }
 
 impl Default for NodeConfig {
@@ -121,6 +129,7 @@ impl Default for NodeConfig {
             marketplace_address: None,
             eth_provider_url: None,
             bootstrap_nodes: Vec::new(),
+            simulation: SimulationSettings::default(),
         }
     }
 }
@@ -145,6 +154,7 @@ impl NodeConfig {
             marketplace_address: None,
             eth_provider_url: None,
             bootstrap_nodes: Vec::new(),
+            simulation: settings.simulation.clone(),
         }
     }
 
@@ -194,6 +204,8 @@ pub struct NodeService {
     public_ip_cache: Option<String>,
     /// Last time public IP was fetched
     public_ip_cache_time: Option<Instant>,
+    /// Mock node serving the API in simulation mode
+    mock_node: Option<MockNode>,
 }
 
 impl NodeService {
@@ -210,6 +222,7 @@ impl NodeService {
             shutdown_tx: None,
             public_ip_cache: None,
             public_ip_cache_time: None,
+            mock_node: None,
         }
     }
 
@@ -228,6 +241,10 @@ impl NodeService {
         self.status.last_error = None;
         log::info!("Starting Archivist node...");
 
+        if self.config.simulation.enabled {
+            return self.start_simulated(app_handle).await;
+        }
+
         // Check if ports are available and clean up orphaned processes
         self.cleanup_orphaned_processes().await;
 
@@ -579,6 +596,42 @@ impl NodeService {
         Ok(())
     }
 
+    /// Serve the node API from an in-process mock node instead of the sidecar
+    async fn start_simulated(&mut self, app_handle: &AppHandle) -> Result<()> {
+        let options =
+            MockNodeOptions::from_settings(&self.config.simulation, self.config.max_storage_bytes);
+        let mock = match MockNode::start(self.config.api_port, options).await {
+            Ok(mock) => mock,
+            Err(e) => {
+                log::error!("{}", e);
+                self.status.state = NodeState::Error;
+                self.status.last_error = Some(e.to_string());
+                return Err(e);
+            }
+        };
+
+        log::info!(
+            "Simulation mode: mock node serving the API on port {} (latency {}ms, failure rate {})",
+            mock.port(),
+            self.config.simulation.latency_ms,
+            self.config.simulation.failure_rate
+        );
+
+        self.status.pid = None;
+        self.status.api_url = Some(format!("http://127.0.0.1:{}", mock.port()));
+        self.status.simulated = true;
+        self.process_state = Some(NodeProcessState {
+            child: None,
+            start_time: Some(Instant::now()),
+            restart_count: self.status.restart_count,
+        });
+        self.mock_node = Some(mock);
+        self.status.state = NodeState::Running;
+
+        let _ = app_handle.emit("node-started", ());
+        Ok(())
+    }
+
     /// Clear the node data directory (for recovery from corruption)
     #[allow(dead_code)]
     pub fn clear_data_directory(&self) -> Result<()> {
@@ -809,6 +862,10 @@ impl NodeService {
             let _ = tx.send(());
         }
 
+        if let Some(mut mock) = self.mock_node.take() {
+            mock.stop();
+        }
+
         // Kill the process
         if let Some(mut process_state) = self.process_state.take() {
             if let Some(child) = process_state.child.take() {
@@ -826,6 +883,7 @@ impl NodeService {
         self.status.peer_id = None;
         self.status.spr = None;
         self.status.addresses = Vec::new();
+        self.status.simulated = false;
 
         log::info!("Archivist node stopped");
         Ok(())
@@ -995,6 +1053,9 @@ impl NodeService {
                 // When announce_ip is configured, use it directly instead of calling ipify
                 if let Some(ref ip) = self.config.announce_ip {
                     self.status.public_ip = Some(ip.clone());
+                } else if self.config.simulation.enabled {
+                    // Simulation mode is meant to work offline
+                    self.status.public_ip = None;
                 } else {
                     let should_fetch_ip = match self.public_ip_cache_time {
                         Some(time) => time.elapsed() > Duration::from_secs(300),
@@ -1055,6 +1116,11 @@ impl NodeService {
             return false;
         }
 
+        // The mock node runs in-process, so it's alive for as long as we hold it
+        if self.mock_node.is_some() {
+            return true;
+        }
+
         // Actually verify the process exists at the OS level
         #[cfg(unix)]
         if let Some(pid) = self.status.pid {
@@ -1092,6 +1158,10 @@ impl NodeService {
         self.status.peer_id = None;
         self.status.spr = None;
         self.status.addresses = Vec::new();
+        self.status.simulated = false;
+        if let Some(mut mock) = self.mock_node.take() {
+            mock.stop();
+        }
         if let Some(msg) = error_msg {
             self.status.last_error = Some(msg);
         }
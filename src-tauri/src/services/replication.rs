@@ -0,0 +1,385 @@
+//! Replication tracking for backup peers
+//!
+//! The source node records which backup peers have acknowledged each folder
+//! manifest (by sequence number). Backup daemons post an acknowledgement to the
+//! source's manifest server once a manifest has been fully processed, and the
+//! source combines those acknowledgements with the latest registered manifests
+//! to report folders that are under-replicated or whose backup peers have
+//! fallen behind.
+
+use crate::error::{ArchivistError, Result};
+use crate::services::backup::BackupService;
+use crate::services::config::BackupPeerConfig;
+use crate::services::manifest_server::ManifestInfo;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Acknowledgement sent by a backup peer after processing a manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestAck {
+    /// Peer ID of the backup node that processed the manifest
+    pub peer_id: String,
+    pub folder_id: String,
+    pub manifest_cid: String,
+    pub sequence_number: u64,
+    pub file_count: u32,
+}
+
+/// What the source knows about one backup peer for one folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerReplicaRecord {
+    pub peer_id: String,
+    /// Highest manifest sequence this peer has acknowledged
+    pub acked_sequence: Option<u64>,
+    pub acked_manifest_cid: Option<String>,
+    pub acked_at: Option<DateTime<Utc>>,
+    pub last_notified_at: Option<DateTime<Utc>>,
+    pub last_notify_error: Option<String>,
+}
+
+impl PeerReplicaRecord {
+    fn new(peer_id: &str) -> Self {
+        Self {
+            peer_id: peer_id.to_string(),
+            acked_sequence: None,
+            acked_manifest_cid: None,
+            acked_at: None,
+            last_notified_at: None,
+            last_notify_error: None,
+        }
+    }
+}
+
+/// Replication status of one backup peer for a folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerReplicationStatus {
+    pub peer_id: String,
+    pub nickname: Option<String>,
+    pub acked_sequence: Option<u64>,
+    pub acked_at: Option<DateTime<Utc>>,
+    /// How many sequence numbers this peer is behind the latest manifest
+    pub sequences_behind: u64,
+    pub up_to_date: bool,
+    pub last_notified_at: Option<DateTime<Utc>>,
+    pub last_notify_error: Option<String>,
+}
+
+/// Replication status of a watched folder's latest manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderReplicationStatus {
+    pub folder_id: String,
+    pub folder_path: String,
+    pub manifest_cid: String,
+    pub sequence_number: u64,
+    pub desired_replicas: u32,
+    /// Backup peers that have acknowledged the latest manifest
+    pub confirmed_replicas: u32,
+    pub under_replicated: bool,
+    pub peers: Vec<PeerReplicationStatus>,
+}
+
+/// Replication report across all folders with registered manifests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicationReport {
+    pub desired_replicas: u32,
+    pub folders: Vec<FolderReplicationStatus>,
+    pub under_replicated_folders: u32,
+    /// Number of (folder, peer) pairs where the peer has fallen behind
+    pub lagging_peers: u32,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Tracks backup peer acknowledgements per folder (stored in backup-replication.json)
+pub struct ReplicationTracker {
+    /// folder_id -> peer_id -> record
+    folders: HashMap<String, HashMap<String, PeerReplicaRecord>>,
+    path: PathBuf,
+}
+
+impl ReplicationTracker {
+    /// Load from disk or create empty.
+    pub fn new(data_dir: &Path) -> Self {
+        let path = data_dir.join("backup-replication.json");
+        let folders = match Self::load(&path) {
+            Ok(folders) => folders,
+            Err(e) => {
+                log::warn!("Failed to load replication state, starting fresh: {}", e);
+                HashMap::new()
+            }
+        };
+        Self { folders, path }
+    }
+
+    fn load(path: &Path) -> Result<HashMap<String, HashMap<String, PeerReplicaRecord>>> {
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            ArchivistError::FileOperationFailed(format!("Failed to read replication state: {}", e))
+        })?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.folders)?;
+        std::fs::write(&self.path, json).map_err(|e| {
+            ArchivistError::FileOperationFailed(format!("Failed to write replication state: {}", e))
+        })
+    }
+
+    fn record_mut(&mut self, folder_id: &str, peer_id: &str) -> &mut PeerReplicaRecord {
+        self.folders
+            .entry(folder_id.to_string())
+            .or_default()
+            .entry(peer_id.to_string())
+            .or_insert_with(|| PeerReplicaRecord::new(peer_id))
+    }
+
+    /// Record an acknowledgement from a backup peer.
+    ///
+    /// Acknowledgements for an older sequence than the one already recorded
+    /// are ignored, so out-of-order delivery never moves a peer backwards.
+    pub fn record_ack(&mut self, ack: &ManifestAck) -> Result<()> {
+        let record = self.record_mut(&ack.folder_id, &ack.peer_id);
+        if record
+            .acked_sequence
+            .is_some_and(|seq| seq > ack.sequence_number)
+        {
+            log::debug!(
+                "Ignoring stale ack from {} for folder {} (seq {})",
+                ack.peer_id,
+                ack.folder_id,
+                ack.sequence_number
+            );
+            return Ok(());
+        }
+
+        record.acked_sequence = Some(ack.sequence_number);
+        record.acked_manifest_cid = Some(ack.manifest_cid.clone());
+        record.acked_at = Some(Utc::now());
+
+        log::info!(
+            "Backup peer {} acknowledged folder {} manifest {} (seq {})",
+            ack.peer_id,
+            ack.folder_id,
+            ack.manifest_cid,
+            ack.sequence_number
+        );
+        self.save()
+    }
+
+    /// Record the outcome of notifying a backup peer about a folder's manifest
+    pub fn record_notification(
+        &mut self,
+        folder_id: &str,
+        peer_id: &str,
+        error: Option<String>,
+    ) -> Result<()> {
+        let record = self.record_mut(folder_id, peer_id);
+        record.last_notified_at = Some(Utc::now());
+        record.last_notify_error = error;
+        self.save()
+    }
+
+    /// Get the raw records for a folder
+    #[allow(dead_code)]
+    pub fn get_folder_records(&self, folder_id: &str) -> Vec<PeerReplicaRecord> {
+        self.folders
+            .get(folder_id)
+            .map(|peers| peers.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Compute the replication status of a folder's latest manifest.
+    ///
+    /// Every configured backup peer is listed even if it has never
+    /// acknowledged anything; peers that acknowledged without being
+    /// configured (e.g. a backup server polling on its own) are listed too.
+    pub fn folder_status(
+        &self,
+        manifest: &ManifestInfo,
+        desired_replicas: u32,
+        backup_peers: &[BackupPeerConfig],
+    ) -> FolderReplicationStatus {
+        let records = self.folders.get(&manifest.folder_id);
+        let mut peers: Vec<PeerReplicationStatus> = Vec::new();
+
+        let mut push_peer = |peer_id: &str, nickname: Option<String>| {
+            let record = records.and_then(|r| r.get(peer_id));
+            let acked_sequence = record.and_then(|r| r.acked_sequence);
+            let sequences_behind = manifest
+                .sequence_number
+                .saturating_sub(acked_sequence.unwrap_or(0));
+            peers.push(PeerReplicationStatus {
+                peer_id: peer_id.to_string(),
+                nickname,
+                acked_sequence,
+                acked_at: record.and_then(|r| r.acked_at),
+                sequences_behind,
+                up_to_date: sequences_behind == 0 && acked_sequence.is_some(),
+                last_notified_at: record.and_then(|r| r.last_notified_at),
+                last_notify_error: record.and_then(|r| r.last_notify_error.clone()),
+            });
+        };
+
+        let configured: Vec<(String, String)> = backup_peers
+            .iter()
+            .map(|p| {
+                let peer_id = BackupService::extract_peer_id_from_multiaddr(&p.address)
+                    .unwrap_or_else(|| p.address.clone());
+                (peer_id, p.nickname.clone())
+            })
+            .collect();
+
+        for (peer_id, nickname) in &configured {
+            push_peer(peer_id, Some(nickname.clone()));
+        }
+        if let Some(records) = records {
+            let mut extra: Vec<&String> = records
+                .keys()
+                .filter(|id| !configured.iter().any(|(peer_id, _)| peer_id == *id))
+                .collect();
+            extra.sort();
+            for peer_id in extra {
+                push_peer(peer_id, None);
+            }
+        }
+
+        let confirmed_replicas = peers.iter().filter(|p| p.up_to_date).count() as u32;
+
+        FolderReplicationStatus {
+            folder_id: manifest.folder_id.clone(),
+            folder_path: manifest.folder_path.clone(),
+            manifest_cid: manifest.manifest_cid.clone(),
+            sequence_number: manifest.sequence_number,
+            desired_replicas,
+            confirmed_replicas,
+            under_replicated: confirmed_replicas < desired_replicas,
+            peers,
+        }
+    }
+
+    /// Build a replication report for all registered manifests
+    pub fn report(
+        &self,
+        manifests: &[ManifestInfo],
+        desired_replicas: u32,
+        backup_peers: &[BackupPeerConfig],
+    ) -> ReplicationReport {
+        let mut folders: Vec<FolderReplicationStatus> = manifests
+            .iter()
+            .map(|m| self.folder_status(m, desired_replicas, backup_peers))
+            .collect();
+        folders.sort_by(|a, b| a.folder_path.cmp(&b.folder_path));
+
+        let under_replicated_folders = folders.iter().filter(|f| f.under_replicated).count() as u32;
+        let lagging_peers = folders
+            .iter()
+            .flat_map(|f| f.peers.iter())
+            .filter(|p| !p.up_to_date)
+            .count() as u32;
+
+        ReplicationReport {
+            desired_replicas,
+            folders,
+            under_replicated_folders,
+            lagging_peers,
+            generated_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn manifest(folder_id: &str, seq: u64) -> ManifestInfo {
+        ManifestInfo {
+            folder_id: folder_id.to_string(),
+            folder_path: format!("/data/{}", folder_id),
+            manifest_cid: format!("cid-{}", seq),
+            sequence_number: seq,
+            updated_at: Utc::now().to_rfc3339(),
+            file_count: 1,
+            total_size_bytes: 10,
+        }
+    }
+
+    fn ack(peer_id: &str, folder_id: &str, seq: u64) -> ManifestAck {
+        ManifestAck {
+            peer_id: peer_id.to_string(),
+            folder_id: folder_id.to_string(),
+            manifest_cid: format!("cid-{}", seq),
+            sequence_number: seq,
+            file_count: 1,
+        }
+    }
+
+    fn peer(nickname: &str, peer_id: &str) -> BackupPeerConfig {
+        BackupPeerConfig {
+            nickname: nickname.to_string(),
+            address: format!("/ip4/10.0.0.1/tcp/8070/p2p/{}", peer_id),
+            trigger_port: 8086,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_stale_ack_ignored() {
+        let tmp = TempDir::new().unwrap();
+        let mut tracker = ReplicationTracker::new(tmp.path());
+
+        tracker.record_ack(&ack("peer-a", "f1", 5)).unwrap();
+        tracker.record_ack(&ack("peer-a", "f1", 3)).unwrap();
+
+        let records = tracker.get_folder_records("f1");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].acked_sequence, Some(5));
+    }
+
+    #[test]
+    fn test_under_replicated_and_lagging() {
+        let tmp = TempDir::new().unwrap();
+        let mut tracker = ReplicationTracker::new(tmp.path());
+        let peers = vec![peer("a", "peer-a"), peer("b", "peer-b")];
+
+        tracker.record_ack(&ack("peer-a", "f1", 4)).unwrap();
+        tracker.record_ack(&ack("peer-b", "f1", 2)).unwrap();
+
+        let status = tracker.folder_status(&manifest("f1", 4), 2, &peers);
+        assert_eq!(status.confirmed_replicas, 1);
+        assert!(status.under_replicated);
+        let b = status.peers.iter().find(|p| p.peer_id == "peer-b").unwrap();
+        assert_eq!(b.sequences_behind, 2);
+        assert!(!b.up_to_date);
+
+        tracker.record_ack(&ack("peer-b", "f1", 4)).unwrap();
+        let status = tracker.folder_status(&manifest("f1", 4), 2, &peers);
+        assert_eq!(status.confirmed_replicas, 2);
+        assert!(!status.under_replicated);
+    }
+
+    #[test]
+    fn test_unconfigured_peer_counts_and_persists() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let mut tracker = ReplicationTracker::new(tmp.path());
+            tracker.record_ack(&ack("peer-x", "f1", 1)).unwrap();
+        }
+        let tracker = ReplicationTracker::new(tmp.path());
+        let report = tracker.report(&[manifest("f1", 1)], 1, &[]);
+        assert_eq!(report.under_replicated_folders, 0);
+        assert_eq!(report.folders[0].peers[0].peer_id, "peer-x");
+    }
+}
@@ -81,6 +81,10 @@ struct ManifestFile {
     sequence_number: u64,
     last_updated: DateTime<Utc>,
     manifest_cid: Option<String>,
+    /// CID of the previously uploaded manifest, so backup peers can walk back
+    /// through sequence numbers they missed
+    #[serde(default)]
+    previous_manifest_cid: Option<String>,
     files: Vec<ManifestFileEntry>,
     deleted_files: Vec<ManifestDeletedEntry>,
     stats: ManifestStats,
@@ -736,6 +740,7 @@ impl SyncService {
         folder.manifest_sequence += 1;
         let sequence_number = folder.manifest_sequence;
         let folder_path = folder.path.clone();
+        let previous_manifest_cid = folder.manifest_cid.clone();
 
         // 4. Get file mappings for this folder (current state)
         let mappings = self
@@ -760,6 +765,7 @@ impl SyncService {
             sequence_number,
             last_updated: Utc::now(),
             manifest_cid: None,
+            previous_manifest_cid,
             files: mappings
                 .iter()
                 .map(|m| ManifestFileEntry {
@@ -1,12 +1,13 @@
 use crate::error::{ArchivistError, Result};
 use crate::node_api::NodeApiClient;
+use crate::storage::{self, Store};
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter};
 use tokio::sync::watch;
@@ -176,7 +177,7 @@ pub struct WebArchiveService {
     pause_tokens: HashMap<String, watch::Sender<bool>>,
     max_concurrent: u32,
     api_port: u16,
-    history_path: Option<PathBuf>,
+    store: Option<Store>,
 }
 
 impl WebArchiveService {
@@ -190,13 +191,22 @@ impl WebArchiveService {
             pause_tokens: HashMap::new(),
             max_concurrent,
             api_port,
-            history_path: None,
+            store: None,
         }
     }
 
-    pub fn with_history(max_concurrent: u32, api_port: u16, data_dir: PathBuf) -> Self {
-        let history_path = data_dir.join("web-archive-history.json");
-        let archived_sites = load_history(&history_path).unwrap_or_default();
+    /// Create the service with archive history persisted in the database.
+    /// `data_dir` is only used to import the pre-database history file.
+    pub fn with_history(
+        max_concurrent: u32,
+        api_port: u16,
+        data_dir: PathBuf,
+        store: Store,
+    ) -> Self {
+        let archived_sites = load_history(&store, &data_dir).unwrap_or_else(|e| {
+            log::warn!("Failed to load web archive history: {}", e);
+            Vec::new()
+        });
         Self {
             tasks: HashMap::new(),
             task_order: Vec::new(),
@@ -205,16 +215,15 @@ impl WebArchiveService {
             pause_tokens: HashMap::new(),
             max_concurrent,
             api_port,
-            history_path: Some(history_path),
+            store: Some(store),
         }
     }
 
-    fn save_history(&self) {
-        if let Some(ref path) = self.history_path {
-            if let Ok(json) = serde_json::to_string_pretty(&self.archived_sites) {
-                if let Err(e) = std::fs::write(path, json) {
-                    log::warn!("Failed to save web archive history: {}", e);
-                }
+    /// Persist one history entry
+    fn save_site(&self, site: &ArchivedSite) {
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.put_document(HISTORY_NAMESPACE, &history_key(site), site) {
+                log::warn!("Failed to save web archive history: {}", e);
             }
         }
     }
@@ -433,13 +442,15 @@ impl WebArchiveService {
             .map_err(|e| ArchivistError::WebArchiveError(format!("Upload failed: {}", e)))?;
 
         // Update the archived site record with the CID
-        for site in &mut self.archived_sites {
-            if site.local_path.as_deref() == Some(local_path) {
-                site.cid = Some(response.cid.clone());
-                break;
-            }
+        if let Some(site) = self
+            .archived_sites
+            .iter_mut()
+            .find(|site| site.local_path.as_deref() == Some(local_path))
+        {
+            site.cid = Some(response.cid.clone());
+            let site = site.clone();
+            self.save_site(&site);
         }
-        self.save_history();
 
         log::info!(
             "Archive uploaded to node: {} -> CID {}",
@@ -450,15 +461,27 @@ impl WebArchiveService {
     }
 }
 
-/// Load history from disk
-fn load_history(path: &PathBuf) -> Result<Vec<ArchivedSite>> {
-    if !path.exists() {
-        return Ok(Vec::new());
+/// Database namespace for archive history, one document per archived site
+const HISTORY_NAMESPACE: &str = "web_archive.history";
+
+/// Stable, chronologically sortable key for a history entry
+fn history_key(site: &ArchivedSite) -> String {
+    format!("{:020}:{}", site.archived_at.timestamp_micros(), site.url)
+}
+
+/// Load history from the database, importing the legacy JSON history file on first run
+fn load_history(store: &Store, data_dir: &Path) -> Result<Vec<ArchivedSite>> {
+    let legacy_path = data_dir.join("web-archive-history.json");
+    if let Some(sites) = storage::take_legacy_json::<Vec<ArchivedSite>>(&legacy_path) {
+        store.transaction(|tx| {
+            for site in &sites {
+                storage::put_document(tx, HISTORY_NAMESPACE, &history_key(site), site)?;
+            }
+            Ok(())
+        })?;
     }
-    let data = std::fs::read_to_string(path)
-        .map_err(|e| ArchivistError::WebArchiveError(format!("Failed to read history: {}", e)))?;
-    serde_json::from_str(&data)
-        .map_err(|e| ArchivistError::WebArchiveError(format!("Failed to parse history: {}", e)))
+
+    store.list_documents(HISTORY_NAMESPACE)
 }
 
 /// Process the queue — called from the background loop in lib.rs.
@@ -584,7 +607,7 @@ pub async fn process_queue(
                         .map(|t| t.url.clone())
                         .unwrap_or_default();
 
-                    svc.archived_sites.push(ArchivedSite {
+                    let site = ArchivedSite {
                         cid: None,
                         url,
                         title: title.clone(),
@@ -593,9 +616,9 @@ pub async fn process_queue(
                         total_bytes: bytes,
                         archived_at: Utc::now(),
                         local_path: Some(local_path.clone()),
-                    });
-
-                    svc.save_history();
+                    };
+                    svc.save_site(&site);
+                    svc.archived_sites.push(site);
 
                     let _ = app_handle.emit(
                         "web-archive-state-changed",
@@ -2441,4 +2464,51 @@ mod tests {
         let state = service.get_queue_state();
         assert_eq!(state.tasks[0].state, ArchiveState::Cancelled);
     }
+
+    #[test]
+    fn test_history_imported_from_legacy_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let store = Store::open_in_memory().unwrap();
+        let sites = vec![
+            ArchivedSite {
+                cid: None,
+                url: "https://example.com".to_string(),
+                title: Some("Example".to_string()),
+                pages_count: 3,
+                assets_count: 5,
+                total_bytes: 1024,
+                archived_at: Utc::now() - chrono::Duration::hours(1),
+                local_path: Some("/tmp/example.zip".to_string()),
+            },
+            ArchivedSite {
+                cid: Some("zdjExample".to_string()),
+                url: "https://example.org".to_string(),
+                title: None,
+                pages_count: 1,
+                assets_count: 0,
+                total_bytes: 10,
+                archived_at: Utc::now(),
+                local_path: None,
+            },
+        ];
+        std::fs::write(
+            tmp.path().join("web-archive-history.json"),
+            serde_json::to_string(&sites).unwrap(),
+        )
+        .unwrap();
+
+        let service =
+            WebArchiveService::with_history(2, 8080, tmp.path().to_path_buf(), store.clone());
+        assert_eq!(service.get_archived_sites().len(), 2);
+        assert!(!tmp.path().join("web-archive-history.json").exists());
+
+        // A fresh service reads the same history back from the database, oldest first
+        let service = WebArchiveService::with_history(2, 8080, tmp.path().to_path_buf(), store);
+        let urls: Vec<String> = service
+            .get_archived_sites()
+            .into_iter()
+            .map(|s| s.url)
+            .collect();
+        assert_eq!(urls, vec!["https://example.com", "https://example.org"]);
+    }
 }
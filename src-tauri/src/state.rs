@@ -7,11 +7,12 @@ use crate::node_api::NodeApiClient;
 use crate::services::node::NodeConfig;
 use crate::services::torrent::{SeedLimitAction, SeedingRules, TorrentConfig, TorrentService};
 use crate::services::{
-    ArchiveViewerServer, BackupDaemon, BackupService, ChatServer, ChatService, ConfigService,
-    FileService, IrcService, ManifestRegistry, ManifestServer, ManifestServerConfig,
+    ArchiveViewerServer, BackupAuditService, BackupDaemon, BackupService, ChatServer, ChatService,
+    ConfigService, FileService, IrcService, ManifestRegistry, ManifestServer, ManifestServerConfig,
     MarketplaceService, MediaDownloadService, MediaStreamingConfig, MediaStreamingServer,
-    NodeService, PeerService, SyncService, WalletService, WebArchiveService,
+    NodeService, PeerService, ReplicationTracker, SyncService, WalletService, WebArchiveService,
 };
+use crate::storage::Store;
 
 /// Global application state managed by Tauri
 pub struct AppState {
@@ -22,8 +23,10 @@ pub struct AppState {
     pub config: Arc<RwLock<ConfigService>>,
     pub backup: Arc<RwLock<BackupService>>,
     pub backup_daemon: Arc<BackupDaemon>,
+    pub backup_audit: Arc<RwLock<BackupAuditService>>,
     pub manifest_registry: Arc<RwLock<ManifestRegistry>>,
     pub manifest_server: Arc<RwLock<ManifestServer>>,
+    pub replication: Arc<RwLock<ReplicationTracker>>,
     pub media: Arc<RwLock<MediaDownloadService>>,
     pub media_streaming: Arc<RwLock<MediaStreamingServer>>,
     pub web_archive: Arc<RwLock<WebArchiveService>>,
@@ -94,6 +97,15 @@ impl AppState {
             node_config.data_dir
         );
 
+        // Open the shared database used for daemon state, chat and archive history
+        let store = Store::open_default().unwrap_or_else(|e| {
+            eprintln!(
+                "[archivist] Failed to open database, state will not persist this session: {}",
+                e
+            );
+            Store::open_in_memory().expect("Failed to open in-memory database")
+        });
+
         // Create shared peer service for backup
         let peers = Arc::new(RwLock::new(PeerService::new()));
 
@@ -113,12 +125,26 @@ impl AppState {
             keystore_dir,
         );
 
+        // Create replication tracker (shared between backup service and manifest server)
+        let backup_data_dir = dirs::data_dir()
+            .map(|p| p.join("archivist"))
+            .unwrap_or_else(|| std::path::PathBuf::from(".archivist"));
+        let replication = Arc::new(RwLock::new(ReplicationTracker::new(&backup_data_dir)));
+
         // Create backup service with API client and peer service
-        let backup_service = BackupService::new(api_client.clone(), peers.clone());
+        let backup_service =
+            BackupService::new(api_client.clone(), peers.clone(), replication.clone());
+
+        // Create backup audit service (reports stored next to the daemon state)
+        let backup_audit = Arc::new(RwLock::new(BackupAuditService::new(
+            api_client.clone(),
+            &backup_data_dir,
+        )));
 
         // Create backup daemon with API client and config
         let backup_daemon = Arc::new(BackupDaemon::new(
             api_client,
+            store.clone(),
             app_config.backup_server.enabled,
             app_config.backup_server.poll_interval_secs,
             app_config.backup_server.max_concurrent_downloads,
@@ -155,7 +181,8 @@ impl AppState {
         };
 
         let manifest_server =
-            ManifestServer::with_config(manifest_registry.clone(), manifest_server_config);
+            ManifestServer::with_config(manifest_registry.clone(), manifest_server_config)
+                .with_replication(replication.clone());
         let manifest_server = Arc::new(RwLock::new(manifest_server));
 
         // Create media download service
@@ -180,6 +207,7 @@ impl AppState {
             app_config.web_archive.max_concurrent_archives,
             app_config.node.api_port,
             web_archive_data_dir,
+            store.clone(),
         )));
 
         // Create archive viewer server
@@ -210,6 +238,7 @@ impl AppState {
             "pending-peer-id".to_string(),
             tls_identity.fingerprint.clone(),
             &app_config.chat,
+            store,
         )
         .expect("Failed to initialize chat service");
 
@@ -253,8 +282,10 @@ impl AppState {
             config: Arc::new(RwLock::new(config_service)),
             backup: Arc::new(RwLock::new(backup_service)),
             backup_daemon,
+            backup_audit,
             manifest_registry,
             manifest_server,
+            replication,
             media,
             media_streaming,
             web_archive,
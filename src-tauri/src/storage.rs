@@ -0,0 +1,368 @@
+//! Embedded transactional storage
+//!
+//! A single SQLite database (`archivist.db` in the app data directory) shared by
+//! services that previously rewrote whole JSON files on every change. Writes go
+//! through SQLite transactions, so a crash mid-update leaves the previous state
+//! intact instead of a truncated file.
+//!
+//! Most callers store serde values as JSON documents keyed by `(namespace, key)`.
+//! Data that benefits from real queries (chat messages) gets its own table.
+//! Schema changes are appended to `MIGRATIONS` and applied in order on open,
+//! tracked with SQLite's `user_version` pragma.
+
+use crate::error::{ArchivistError, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// File name of the shared database inside the app data directory
+pub const DATABASE_FILE: &str = "archivist.db";
+
+/// Schema migrations, applied in order. Never edit an existing entry; append a new one.
+const MIGRATIONS: &[&str] = &[
+    // 1: JSON documents (backup daemon state, web archive history, conversations)
+    "CREATE TABLE documents (
+        namespace TEXT NOT NULL,
+        key TEXT NOT NULL,
+        value TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        PRIMARY KEY (namespace, key)
+    );",
+    // 2: chat messages, one row per message so appends don't rewrite the conversation
+    "CREATE TABLE chat_messages (
+        conversation_id TEXT NOT NULL,
+        id TEXT NOT NULL,
+        timestamp TEXT NOT NULL,
+        value TEXT NOT NULL,
+        PRIMARY KEY (conversation_id, id)
+    );
+    CREATE INDEX idx_chat_messages_timestamp ON chat_messages (conversation_id, timestamp);",
+];
+
+pub(crate) fn storage_error(e: rusqlite::Error) -> ArchivistError {
+    ArchivistError::StorageError(e.to_string())
+}
+
+/// Handle to the shared database. Cheap to clone.
+#[derive(Clone)]
+pub struct Store {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl Store {
+    /// Open (or create) the database at `path` and apply pending migrations
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(path).map_err(storage_error)?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .map_err(storage_error)?;
+        conn.pragma_update(None, "synchronous", "NORMAL")
+            .map_err(storage_error)?;
+
+        Self::from_connection(conn)
+    }
+
+    /// Open the database in the default app data directory
+    pub fn open_default() -> Result<Self> {
+        Self::open(&default_database_path())
+    }
+
+    /// Open a throwaway in-memory database (used when no data directory is available, and in tests)
+    pub fn open_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory().map_err(storage_error)?;
+        Self::from_connection(conn)
+    }
+
+    fn from_connection(mut conn: Connection) -> Result<Self> {
+        apply_migrations(&mut conn)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        // A panic while holding the lock can't leave a half-applied SQLite
+        // transaction behind, so recovering the guard is safe
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Current schema version
+    #[allow(dead_code)]
+    pub fn schema_version(&self) -> Result<u32> {
+        schema_version(&self.lock())
+    }
+
+    /// Read a JSON document
+    pub fn get_document<T: DeserializeOwned>(
+        &self,
+        namespace: &str,
+        key: &str,
+    ) -> Result<Option<T>> {
+        get_document(&self.lock(), namespace, key)
+    }
+
+    /// Insert or replace a JSON document
+    pub fn put_document<T: Serialize>(&self, namespace: &str, key: &str, value: &T) -> Result<()> {
+        put_document(&self.lock(), namespace, key, value)
+    }
+
+    /// All documents in a namespace, ordered by key
+    pub fn list_documents<T: DeserializeOwned>(&self, namespace: &str) -> Result<Vec<T>> {
+        list_documents(&self.lock(), namespace)
+    }
+
+    /// Delete a JSON document (no-op if it doesn't exist)
+    #[allow(dead_code)]
+    pub fn delete_document(&self, namespace: &str, key: &str) -> Result<()> {
+        delete_document(&self.lock(), namespace, key)
+    }
+
+    /// Run `f` inside a transaction, committing only if it returns `Ok`
+    pub fn transaction<R>(&self, f: impl FnOnce(&Transaction<'_>) -> Result<R>) -> Result<R> {
+        let mut conn = self.lock();
+        let tx = conn.transaction().map_err(storage_error)?;
+        let result = f(&tx)?;
+        tx.commit().map_err(storage_error)?;
+        Ok(result)
+    }
+}
+
+/// Default database location: `<data dir>/archivist/archivist.db`
+pub fn default_database_path() -> PathBuf {
+    dirs::data_dir()
+        .map(|p| p.join("archivist"))
+        .unwrap_or_else(|| PathBuf::from(".archivist"))
+        .join(DATABASE_FILE)
+}
+
+fn schema_version(conn: &Connection) -> Result<u32> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(storage_error)
+}
+
+fn apply_migrations(conn: &mut Connection) -> Result<()> {
+    let current = schema_version(conn)? as usize;
+
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(current) {
+        let version = index + 1;
+        let tx = conn.transaction().map_err(storage_error)?;
+        tx.execute_batch(sql).map_err(|e| {
+            ArchivistError::StorageError(format!("Migration {} failed: {}", version, e))
+        })?;
+        tx.pragma_update(None, "user_version", version as u32)
+            .map_err(storage_error)?;
+        tx.commit().map_err(storage_error)?;
+        log::info!("Applied storage migration {}", version);
+    }
+
+    Ok(())
+}
+
+/// Read a JSON document (works on a connection or an open transaction)
+pub fn get_document<T: DeserializeOwned>(
+    conn: &Connection,
+    namespace: &str,
+    key: &str,
+) -> Result<Option<T>> {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT value FROM documents WHERE namespace = ?1 AND key = ?2",
+            params![namespace, key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(storage_error)?;
+
+    value
+        .map(|json| serde_json::from_str(&json).map_err(ArchivistError::from))
+        .transpose()
+}
+
+/// Insert or replace a JSON document
+pub fn put_document<T: Serialize>(
+    conn: &Connection,
+    namespace: &str,
+    key: &str,
+    value: &T,
+) -> Result<()> {
+    let json = serde_json::to_string(value)?;
+    conn.execute(
+        "INSERT INTO documents (namespace, key, value, updated_at) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT (namespace, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![namespace, key, json, Utc::now().to_rfc3339()],
+    )
+    .map_err(storage_error)?;
+    Ok(())
+}
+
+/// All documents in a namespace, ordered by key
+pub fn list_documents<T: DeserializeOwned>(conn: &Connection, namespace: &str) -> Result<Vec<T>> {
+    let mut stmt = conn
+        .prepare("SELECT key, value FROM documents WHERE namespace = ?1 ORDER BY key")
+        .map_err(storage_error)?;
+    let rows = stmt
+        .query_map(params![namespace], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(storage_error)?;
+
+    let mut documents = Vec::new();
+    for row in rows {
+        let (key, json) = row.map_err(storage_error)?;
+        match serde_json::from_str(&json) {
+            Ok(doc) => documents.push(doc),
+            Err(e) => log::warn!("Skipping unreadable document {}/{}: {}", namespace, key, e),
+        }
+    }
+    Ok(documents)
+}
+
+/// Delete a JSON document
+pub fn delete_document(conn: &Connection, namespace: &str, key: &str) -> Result<()> {
+    conn.execute(
+        "DELETE FROM documents WHERE namespace = ?1 AND key = ?2",
+        params![namespace, key],
+    )
+    .map_err(storage_error)?;
+    Ok(())
+}
+
+/// Delete every document in a namespace
+pub fn clear_namespace(conn: &Connection, namespace: &str) -> Result<()> {
+    conn.execute(
+        "DELETE FROM documents WHERE namespace = ?1",
+        params![namespace],
+    )
+    .map_err(storage_error)?;
+    Ok(())
+}
+
+/// Read a pre-database JSON state file for import, then move it aside
+///
+/// The file is renamed to `<name>.migrated` only after it parsed successfully,
+/// so an unreadable file is left in place for inspection.
+pub fn take_legacy_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let data = std::fs::read_to_string(path).ok()?;
+    let value = match serde_json::from_str(&data) {
+        Ok(value) => value,
+        Err(e) => {
+            log::warn!("Failed to parse legacy state file {:?}: {}", path, e);
+            return None;
+        }
+    };
+
+    let mut migrated = path.as_os_str().to_owned();
+    migrated.push(".migrated");
+    if let Err(e) = std::fs::rename(path, &migrated) {
+        log::warn!("Failed to move legacy state file {:?} aside: {}", path, e);
+    }
+
+    log::info!("Imported legacy state file {:?} into the database", path);
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Doc {
+        name: String,
+        count: u32,
+    }
+
+    #[test]
+    fn test_migrations_applied_once() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(DATABASE_FILE);
+
+        let store = Store::open(&path).unwrap();
+        assert_eq!(store.schema_version().unwrap(), MIGRATIONS.len() as u32);
+        store
+            .put_document(
+                "test",
+                "a",
+                &Doc {
+                    name: "a".into(),
+                    count: 1,
+                },
+            )
+            .unwrap();
+        drop(store);
+
+        // Reopening must not re-run migrations or lose data
+        let store = Store::open(&path).unwrap();
+        assert_eq!(store.schema_version().unwrap(), MIGRATIONS.len() as u32);
+        let doc: Option<Doc> = store.get_document("test", "a").unwrap();
+        assert_eq!(doc.unwrap().count, 1);
+    }
+
+    #[test]
+    fn test_failed_transaction_rolls_back() {
+        let store = Store::open_in_memory().unwrap();
+        store
+            .put_document(
+                "test",
+                "a",
+                &Doc {
+                    name: "a".into(),
+                    count: 1,
+                },
+            )
+            .unwrap();
+
+        let result: Result<()> = store.transaction(|tx| {
+            put_document(
+                tx,
+                "test",
+                "a",
+                &Doc {
+                    name: "a".into(),
+                    count: 2,
+                },
+            )?;
+            put_document(
+                tx,
+                "test",
+                "b",
+                &Doc {
+                    name: "b".into(),
+                    count: 1,
+                },
+            )?;
+            Err(ArchivistError::StorageError("abort".into()))
+        });
+        assert!(result.is_err());
+
+        let docs: Vec<Doc> = store.list_documents("test").unwrap();
+        assert_eq!(
+            docs,
+            vec![Doc {
+                name: "a".into(),
+                count: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn test_take_legacy_json_moves_file_aside() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("state.json");
+        std::fs::write(&path, r#"{"name":"legacy","count":3}"#).unwrap();
+
+        let doc: Doc = take_legacy_json(&path).unwrap();
+        assert_eq!(doc.count, 3);
+        assert!(!path.exists());
+        assert!(tmp.path().join("state.json.migrated").exists());
+
+        assert!(take_legacy_json::<Doc>(&path).is_none());
+    }
+}
@@ -0,0 +1,170 @@
+//! Integration tests for NodeApiClient against the in-process mock node.
+
+use archivist_lib::node_api::{AvailabilityRequest, NodeApiClient, StorageRequestParams};
+use archivist_lib::services::mock_node::{MockNode, MockNodeOptions};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+async fn start_mock(options: MockNodeOptions) -> (MockNode, NodeApiClient) {
+    let node = MockNode::start(0, options).await.unwrap();
+    let client = NodeApiClient::new(node.port());
+    (node, client)
+}
+
+#[tokio::test]
+async fn test_upload_list_download_delete() {
+    let (_node, client) = start_mock(MockNodeOptions::default()).await;
+
+    let info = client.get_info().await.unwrap();
+    assert!(!info.id.is_empty());
+    assert!(client.health_check().await.unwrap());
+
+    let uploaded = client
+        .upload_bytes(b"hello simulation".to_vec(), "hello.txt", None)
+        .await
+        .unwrap();
+
+    let listed = client.get_file_info(&uploaded.cid).await.unwrap().unwrap();
+    assert_eq!(listed.filename.as_deref(), Some("hello.txt"));
+    assert_eq!(listed.mimetype.as_deref(), Some("text/plain"));
+    assert_eq!(listed.dataset_size, Some(16));
+
+    let data = client.download_file(&uploaded.cid).await.unwrap();
+    assert_eq!(data, b"hello simulation");
+
+    let space = client.get_space().await.unwrap();
+    assert_eq!(space.quota_used_bytes, 16);
+
+    client.delete_file(&uploaded.cid).await.unwrap();
+    assert!(client.download_file(&uploaded.cid).await.is_err());
+}
+
+#[tokio::test]
+async fn test_resumed_download_uses_range() {
+    let (_node, client) = start_mock(MockNodeOptions::default()).await;
+    let content = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let cid = client
+        .upload_bytes(content.to_vec(), "file.bin", None)
+        .await
+        .unwrap()
+        .cid;
+
+    let tmp = TempDir::new().unwrap();
+    let dest = tmp.path().join("file.bin");
+    let part = format!("{}.part", dest.display());
+    std::fs::write(&part, &content[..12]).unwrap();
+    std::fs::write(
+        format!("{}.json", part),
+        format!(r#"{{"cid":"{}","totalBytes":{}}}"#, cid, content.len()),
+    )
+    .unwrap();
+
+    client.download_file_to_path(&cid, &dest).await.unwrap();
+    assert_eq!(std::fs::read(&dest).unwrap(), content);
+}
+
+#[tokio::test]
+async fn test_quota_exceeded_upload() {
+    let (_node, client) = start_mock(MockNodeOptions {
+        quota_bytes: 8,
+        ..MockNodeOptions::default()
+    })
+    .await;
+
+    let err = client
+        .upload_bytes(b"more than eight bytes".to_vec(), "big.bin", None)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Storage quota full"));
+}
+
+#[tokio::test]
+async fn test_marketplace_endpoints() {
+    let (_node, client) = start_mock(MockNodeOptions::default()).await;
+
+    assert!(client.get_availability().await.unwrap().is_empty());
+    client
+        .post_availability(&AvailabilityRequest {
+            maximum_duration: "86400".to_string(),
+            minimum_price_per_byte_per_second: "1".to_string(),
+            maximum_collateral_per_byte: "1".to_string(),
+            available_until: 0,
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        client.get_availability().await.unwrap()[0].maximum_duration,
+        "86400"
+    );
+    assert!(client.get_sales_slots().await.unwrap().is_empty());
+
+    let cid = client
+        .upload_bytes(vec![7u8; 4096], "data.bin", None)
+        .await
+        .unwrap()
+        .cid;
+    let request_id = client
+        .create_storage_request(
+            &cid,
+            &StorageRequestParams {
+                duration: "3600".to_string(),
+                proof_probability: "100".to_string(),
+                price_per_byte_per_second: "1".to_string(),
+                collateral_per_byte: "1".to_string(),
+                nodes: 3,
+                tolerance: 1,
+                expiry: 300,
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        client.get_purchases().await.unwrap(),
+        vec![request_id.clone()]
+    );
+    let purchase = client.get_purchase(&request_id).await.unwrap();
+    let request = purchase.request.unwrap();
+    assert_eq!(request.content.cid, cid);
+    assert_eq!(request.ask.slots, 4);
+    assert_eq!(request.ask.duration, 3600);
+}
+
+#[tokio::test]
+async fn test_connected_peers_are_listed() {
+    let (_node, client) = start_mock(MockNodeOptions::default()).await;
+
+    assert!(client.list_peers().await.unwrap().is_empty());
+    client
+        .connect_peer("16Uiu2HAmPeer", "/ip4/10.0.0.2/tcp/8070")
+        .await
+        .unwrap();
+
+    let peers = client.list_peers().await.unwrap();
+    assert_eq!(peers.len(), 1);
+    assert_eq!(peers[0].addresses, vec!["/ip4/10.0.0.2/tcp/8070"]);
+    assert!(client.get_spr().await.unwrap().starts_with("spr:"));
+}
+
+#[tokio::test]
+async fn test_failure_and_latency_injection() {
+    let (node, client) = start_mock(MockNodeOptions {
+        failure_rate: 1.0,
+        ..MockNodeOptions::default()
+    })
+    .await;
+
+    let err = client.get_space().await.unwrap_err();
+    assert!(err.to_string().contains("503"));
+    assert!(!client.health_check().await.unwrap());
+
+    node.set_options(MockNodeOptions {
+        latency: Duration::from_millis(150),
+        ..MockNodeOptions::default()
+    })
+    .await;
+
+    let started = Instant::now();
+    client.get_space().await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(150));
+}
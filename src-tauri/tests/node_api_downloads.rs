@@ -0,0 +1,126 @@
+//! Integration tests for resumable downloads against a mocked node API.
+
+use archivist_lib::node_api::NodeApiClient;
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const CID: &str = "zDvZRwzmTestCid";
+const CONTENT: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+fn client_for(server: &MockServer) -> NodeApiClient {
+    NodeApiClient::new(server.address().port())
+}
+
+fn write_partial(dest: &std::path::Path, prefix: &[u8]) {
+    let part = format!("{}.part", dest.display());
+    std::fs::write(&part, prefix).unwrap();
+    std::fs::write(
+        format!("{}.json", part),
+        format!(r#"{{"cid":"{}","totalBytes":{}}}"#, CID, CONTENT.len()),
+    )
+    .unwrap();
+}
+
+fn assert_finished(dest: &std::path::Path) {
+    assert_eq!(std::fs::read(dest).unwrap(), CONTENT);
+    assert!(!std::path::Path::new(&format!("{}.part", dest.display())).exists());
+    assert!(!std::path::Path::new(&format!("{}.part.json", dest.display())).exists());
+}
+
+#[tokio::test]
+async fn test_fresh_download_moves_into_place() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/archivist/v1/data/{}", CID)))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+        .mount(&server)
+        .await;
+
+    let tmp = TempDir::new().unwrap();
+    let dest = tmp.path().join("file.bin");
+    client_for(&server)
+        .download_file_to_path(CID, &dest)
+        .await
+        .unwrap();
+
+    assert_finished(&dest);
+}
+
+#[tokio::test]
+async fn test_resume_with_range_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/archivist/v1/data/{}", CID)))
+        .and(header("range", "bytes=10-"))
+        .respond_with(
+            ResponseTemplate::new(206)
+                .insert_header(
+                    "content-range",
+                    format!("bytes 10-{}/{}", CONTENT.len() - 1, CONTENT.len()).as_str(),
+                )
+                .set_body_bytes(&CONTENT[10..]),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let tmp = TempDir::new().unwrap();
+    let dest = tmp.path().join("file.bin");
+    write_partial(&dest, &CONTENT[..10]);
+
+    client_for(&server)
+        .download_file_to_path(CID, &dest)
+        .await
+        .unwrap();
+
+    assert_finished(&dest);
+}
+
+#[tokio::test]
+async fn test_resume_when_node_ignores_range() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/archivist/v1/data/{}", CID)))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+        .mount(&server)
+        .await;
+
+    let tmp = TempDir::new().unwrap();
+    let dest = tmp.path().join("file.bin");
+    write_partial(&dest, &CONTENT[..10]);
+
+    client_for(&server)
+        .download_file_to_path(CID, &dest)
+        .await
+        .unwrap();
+
+    assert_finished(&dest);
+}
+
+#[tokio::test]
+async fn test_partial_for_other_cid_is_not_resumed() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/archivist/v1/data/{}", CID)))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(CONTENT))
+        .mount(&server)
+        .await;
+
+    let tmp = TempDir::new().unwrap();
+    let dest = tmp.path().join("file.bin");
+    let part = format!("{}.part", dest.display());
+    std::fs::write(&part, b"stale data from another file").unwrap();
+    std::fs::write(
+        format!("{}.json", part),
+        r#"{"cid":"zDvZRwzmOtherCid","totalBytes":28}"#,
+    )
+    .unwrap();
+
+    client_for(&server)
+        .download_file_to_path(CID, &dest)
+        .await
+        .unwrap();
+
+    assert_finished(&dest);
+}
@@ -0,0 +1,82 @@
+//! Integration tests for stream-based uploads against a mocked node API.
+
+use archivist_lib::node_api::NodeApiClient;
+use bytes::Bytes;
+use wiremock::matchers::{body_bytes, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const UPLOAD_PATH: &str = "/api/archivist/v1/data";
+
+#[tokio::test]
+async fn test_upload_bytes_guesses_mime_type() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(UPLOAD_PATH))
+        .and(header("content-type", "application/json"))
+        .and(header(
+            "content-disposition",
+            "attachment; filename=\"manifest.json\"",
+        ))
+        .and(body_bytes(b"{\"a\":1}".to_vec()))
+        .respond_with(ResponseTemplate::new(200).set_body_string("zDvZRwzmManifest\n"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = NodeApiClient::new(server.address().port());
+    let response = client
+        .upload_bytes(b"{\"a\":1}".to_vec(), "manifest.json", None)
+        .await
+        .unwrap();
+
+    assert_eq!(response.cid, "zDvZRwzmManifest");
+}
+
+#[tokio::test]
+async fn test_upload_stream_without_length() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(UPLOAD_PATH))
+        .and(header("content-type", "application/zip"))
+        .and(body_bytes(b"part one, part two".to_vec()))
+        .respond_with(ResponseTemplate::new(200).set_body_string("zDvZRwzmStream"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let chunks: Vec<std::io::Result<Bytes>> = vec![
+        Ok(Bytes::from_static(b"part one, ")),
+        Ok(Bytes::from_static(b"part two")),
+    ];
+    let client = NodeApiClient::new(server.address().port());
+    let response = client
+        .upload_stream(
+            futures::stream::iter(chunks),
+            "archive.bin",
+            Some("application/zip"),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.cid, "zDvZRwzmStream");
+}
+
+#[tokio::test]
+async fn test_upload_reader_reports_quota_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(UPLOAD_PATH))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Unable to store block"))
+        .mount(&server)
+        .await;
+
+    let client = NodeApiClient::new(server.address().port());
+    let err = client
+        .upload_reader(&b"data"[..], "notes.txt", None, Some(4), None)
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("Storage quota full"));
+}